@@ -0,0 +1,255 @@
+use std::collections::{BTreeMap, HashMap};
+use std::future::Future;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use namada_core::address::Address;
+use namada_core::chain::{BlockHeight, Epoch};
+use namada_core::token::Amount;
+use namada_sdk::governance::parameters::GovernanceParameters;
+use namada_sdk::governance::storage::proposal::StorageProposal;
+use namada_sdk::governance::utils::ProposalResult;
+use namada_proof_of_stake::parameters::PosParams;
+use namada_proof_of_stake::types::{CommissionPair, ValidatorMetaData, WeightedValidator};
+
+/// Maximum number of entries kept per query kind. When a kind is full, its
+/// expired entries are dropped first, then the oldest one.
+pub(crate) const MAX_ENTRIES: usize = 10_000;
+
+/// How long a cached entry stays valid
+#[derive(Debug, Clone, Copy)]
+pub struct CachePolicy {
+    ttl: Option<Duration>,
+    epoch_scoped: bool,
+}
+
+impl CachePolicy {
+    /// Valid for `ttl`
+    pub const fn ttl(ttl: Duration) -> Self {
+        Self { ttl: Some(ttl), epoch_scoped: false }
+    }
+
+    /// Valid for `ttl`, or until the epoch advances
+    pub const fn epoch(ttl: Duration) -> Self {
+        Self { ttl: Some(ttl), epoch_scoped: true }
+    }
+
+    /// Always valid, for data pinned to a block height
+    pub const fn pinned() -> Self {
+        Self { ttl: None, epoch_scoped: false }
+    }
+}
+
+pub(crate) struct Entry<V> {
+    value: V,
+    pub(crate) inserted_at: Instant,
+    /// Last epoch observed when the value was fetched
+    epoch: u64,
+}
+
+/// Hit and miss counters of a query kind
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheKindStats {
+    pub hits: u64,
+    pub misses: u64,
+    pub entries: usize,
+    pub hit_ratio: f64,
+}
+
+/// Statistics of the whole cache
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStats {
+    /// Last epoch observed by the cache
+    pub epoch: u64,
+    pub kinds: BTreeMap<&'static str, CacheKindStats>,
+}
+
+/// Cached results of one kind of query
+pub struct CacheMap<K, V> {
+    name: &'static str,
+    policy: CachePolicy,
+    pub(crate) entries: Mutex<HashMap<K, Entry<V>>>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl<K: Eq + Hash + Clone, V: Clone> CacheMap<K, V> {
+    pub(crate) fn new(name: &'static str, policy: CachePolicy) -> Self {
+        Self {
+            name,
+            policy,
+            entries: Mutex::new(HashMap::new()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    fn is_expired(&self, entry: &Entry<V>, epoch: u64) -> bool {
+        let ttl_elapsed = self.policy.ttl.is_some_and(|ttl| entry.inserted_at.elapsed() >= ttl);
+        let epoch_advanced = self.policy.epoch_scoped && entry.epoch < epoch;
+        ttl_elapsed || epoch_advanced
+    }
+
+    pub(crate) fn get(&self, key: &K, epoch: u64) -> Option<V> {
+        let mut entries = self.entries.lock().unwrap();
+        let value = match entries.get(key) {
+            Some(entry) if self.is_expired(entry, epoch) => {
+                entries.remove(key);
+                None
+            }
+            Some(entry) => Some(entry.value.clone()),
+            None => None,
+        };
+        let counter = if value.is_some() { &self.hits } else { &self.misses };
+        counter.fetch_add(1, Ordering::Relaxed);
+        value
+    }
+
+    pub(crate) fn insert(&self, key: K, value: V, epoch: u64) {
+        let mut entries = self.entries.lock().unwrap();
+        if entries.len() >= MAX_ENTRIES && !entries.contains_key(&key) {
+            entries.retain(|_, entry| !self.is_expired(entry, epoch));
+            let oldest = entries.iter()
+                .min_by_key(|(_, entry)| entry.inserted_at)
+                .map(|(key, _)| key.clone());
+            if let (true, Some(oldest)) = (entries.len() >= MAX_ENTRIES, oldest) {
+                entries.remove(&oldest);
+            }
+        }
+        entries.insert(key, Entry { value, inserted_at: Instant::now(), epoch });
+    }
+}
+
+/// Operations shared by the query kinds, whatever their key and value types
+pub(crate) trait CacheStore: Send + Sync {
+    fn purge_epoch_scoped(&self);
+    fn stats(&self) -> (&'static str, CacheKindStats);
+}
+
+impl<K: Eq + Hash + Clone + Send, V: Clone + Send> CacheStore for CacheMap<K, V> {
+    fn purge_epoch_scoped(&self) {
+        if self.policy.epoch_scoped {
+            self.entries.lock().unwrap().clear();
+        }
+    }
+
+    fn stats(&self) -> (&'static str, CacheKindStats) {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let misses = self.misses.load(Ordering::Relaxed);
+        let lookups = hits + misses;
+        let stats = CacheKindStats {
+            hits,
+            misses,
+            entries: self.entries.lock().unwrap().len(),
+            hit_ratio: if lookups == 0 { 0.0 } else { hits as f64 / lookups as f64 },
+        };
+        (self.name, stats)
+    }
+}
+
+/// Which validator set a `validator_sets` entry holds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ValidatorSetKind {
+    Consensus,
+    BelowCapacity,
+}
+
+/// In-process cache of RPC query results, with a TTL per query kind
+///
+/// Epoch-scoped kinds are cleared as soon as the client observes a new epoch.
+/// Kinds keyed by a block height never expire, as the chain state at a
+/// committed height can't change, and neither do submitted proposals.
+pub struct QueryCache {
+    /// Last epoch observed by the client
+    epoch: AtomicU64,
+    pub native_token: CacheMap<(), Address>,
+    pub pos_params: CacheMap<(), PosParams>,
+    pub validators: CacheMap<Epoch, Vec<Address>>,
+    pub validator_sets: CacheMap<(ValidatorSetKind, Epoch), Vec<WeightedValidator>>,
+    pub metadata: CacheMap<(Address, Option<Epoch>), (Option<ValidatorMetaData>, CommissionPair)>,
+    pub epoch_at_height: CacheMap<BlockHeight, Epoch>,
+    pub balances: CacheMap<(Address, Address, BlockHeight), Amount>,
+    pub total_supply: CacheMap<(Address, BlockHeight), Amount>,
+    pub gov_params: CacheMap<(), GovernanceParameters>,
+    pub proposals: CacheMap<u64, StorageProposal>,
+    pub proposal_results: CacheMap<u64, ProposalResult>,
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        Self {
+            epoch: AtomicU64::new(0),
+            native_token: CacheMap::new("native_token", CachePolicy::ttl(Duration::from_secs(3600))),
+            pos_params: CacheMap::new("pos_params", CachePolicy::epoch(Duration::from_secs(3600))),
+            validators: CacheMap::new("validators", CachePolicy::epoch(Duration::from_secs(60))),
+            validator_sets: CacheMap::new("validator_sets", CachePolicy::epoch(Duration::from_secs(60))),
+            metadata: CacheMap::new("metadata", CachePolicy::epoch(Duration::from_secs(60))),
+            epoch_at_height: CacheMap::new("epoch_at_height", CachePolicy::pinned()),
+            balances: CacheMap::new("balances", CachePolicy::pinned()),
+            total_supply: CacheMap::new("total_supply", CachePolicy::pinned()),
+            gov_params: CacheMap::new("gov_params", CachePolicy::epoch(Duration::from_secs(3600))),
+            proposals: CacheMap::new("proposals", CachePolicy::pinned()),
+            proposal_results: CacheMap::new("proposal_results", CachePolicy::epoch(Duration::from_secs(60))),
+        }
+    }
+}
+
+impl QueryCache {
+    fn stores(&self) -> [&dyn CacheStore; 11] {
+        [
+            &self.native_token,
+            &self.pos_params,
+            &self.validators,
+            &self.validator_sets,
+            &self.metadata,
+            &self.epoch_at_height,
+            &self.balances,
+            &self.total_supply,
+            &self.gov_params,
+            &self.proposals,
+            &self.proposal_results,
+        ]
+    }
+
+    /// Record an epoch returned by the node, clearing the epoch-scoped kinds
+    /// when it is newer than the last one observed
+    pub fn observe_epoch(&self, epoch: Epoch) {
+        let previous = self.epoch.fetch_max(epoch.0, Ordering::SeqCst);
+        if epoch.0 > previous {
+            for store in self.stores() {
+                store.purge_epoch_scoped();
+            }
+        }
+    }
+
+    /// Get a cached value, or fetch and cache it. Errors are not cached.
+    pub async fn get_or_fetch<K, V, E, F, Fut>(&self, map: &CacheMap<K, V>, key: K, fetch: F) -> Result<V, E>
+    where
+        K: Eq + Hash + Clone,
+        V: Clone,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<V, E>>,
+    {
+        let epoch = self.epoch.load(Ordering::SeqCst);
+        if let Some(value) = map.get(&key, epoch) {
+            return Ok(value);
+        }
+
+        let value = fetch().await?;
+        // A value fetched while the epoch advanced may already be stale
+        if !map.policy.epoch_scoped || self.epoch.load(Ordering::SeqCst) == epoch {
+            map.insert(key, value.clone(), epoch);
+        }
+        Ok(value)
+    }
+
+    /// Hit and miss statistics of every query kind
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            epoch: self.epoch.load(Ordering::SeqCst),
+            kinds: self.stores().into_iter().map(|store| store.stats()).collect(),
+        }
+    }
+}
@@ -1,71 +1,415 @@
 use thiserror::Error;
-use tracing::{error, info};
-use tendermint_rpc::{HttpClient, Url};
+use tracing::field::Empty;
+use tracing::{info, info_span, warn, Instrument, Span};
+use tendermint_rpc::endpoint::{block, commit};
+use tendermint_rpc::Paging;
+use tendermint_rpc::error::ErrorDetail;
+use tendermint_rpc::{Client as _, HttpClient, Url};
 use namada_core::address::Address;
 use namada_core::chain::Epoch;
 use namada_core::chain::BlockHeight;
-use namada_proof_of_stake::types::{LivenessInfo, ValidatorMetaData, CommissionPair, ValidatorStateInfo};
-use namada_sdk::rpc;
+use namada_core::hash::Hash;
+use namada_proof_of_stake::parameters::PosParams;
+use namada_proof_of_stake::types::{LivenessInfo, ValidatorMetaData, CommissionPair, ValidatorStateInfo, BondsAndUnbondsDetails, Slash};
+use namada_sdk::rpc::{self, TxAppliedEvents};
+use namada_sdk::error::QueryError;
+use namada_sdk::token::storage_key::minted_balance_key;
+use namada_sdk::governance::parameters::GovernanceParameters;
+use namada_sdk::governance::storage::keys as gov_storage;
+use namada_sdk::governance::storage::proposal::StorageProposal;
+use namada_sdk::governance::utils::{ProposalResult, Vote};
+use namada_core::borsh::BorshDeserialize;
 use namada_sdk::queries::RPC;
+use std::future::Future;
 use std::str::FromStr;
-use tokio::task::spawn_blocking;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use serde::Serialize;
+use crate::cache::{CacheStats, QueryCache, ValidatorSetKind};
+use crate::config::RpcEndpointConfig;
+use crate::metrics::METRICS;
 
 #[derive(Error, Debug)]
 pub enum ClientError {
     #[error("RPC connection error: {0}")]
-    ConnectionError(String),
+    ConnectionError(#[source] tendermint_rpc::Error),
     #[error("RPC query error: {0}")]
     QueryError(String),
     #[error("Invalid RPC URL: {0}")]
-    InvalidUrl(String),
+    InvalidUrl(#[source] tendermint_rpc::Error),
+    /// A query of the SDK `rpc` module failed
+    #[error("RPC query error: {0}")]
+    Sdk(#[source] Box<namada_sdk::error::Error>),
+    /// A query of the RPC router failed
+    #[error("RPC query error: {0}")]
+    Router(#[from] namada_io::client::Error),
+    #[error("Decoding error: {0}")]
+    Decoding(#[from] std::io::Error),
+    /// A query of the CometBFT RPC failed
+    #[error("CometBFT RPC error: {0}")]
+    CometBft(#[source] tendermint_rpc::Error),
+}
+
+impl From<namada_sdk::error::Error> for ClientError {
+    fn from(err: namada_sdk::error::Error) -> Self {
+        Self::Sdk(Box::new(err))
+    }
+}
+
+impl ClientError {
+    /// Whether the node could not be reached or could not answer, as opposed
+    /// to an answer that is an error, which another node would give as well
+    pub fn is_connection_error(&self) -> bool {
+        match self {
+            Self::ConnectionError(_) => true,
+            Self::CometBft(err) | Self::Router(namada_io::client::Error::Tendermint(err)) => is_transport_error(err),
+            Self::Sdk(err) => match &**err {
+                // The SDK reports every error of the client as no response,
+                // the error of a query answered by the node included
+                namada_sdk::error::Error::Query(QueryError::NoResponse(err)) => !err.starts_with("Info log:"),
+                namada_sdk::error::Error::Query(QueryError::CatchingUp) => true,
+                _ => false,
+            },
+            _ => false,
+        }
+    }
 }
 
+/// Whether a CometBFT RPC error comes from the transport, not from a response
+fn is_transport_error(err: &tendermint_rpc::Error) -> bool {
+    matches!(
+        err.detail(),
+        ErrorDetail::Io(_)
+            | ErrorDetail::Http(_)
+            | ErrorDetail::HttpRequestFailed(_)
+            | ErrorDetail::Timeout(_)
+            | ErrorDetail::WebSocket(_)
+            | ErrorDetail::WebSocketTimeout(_)
+            | ErrorDetail::ChannelSend(_)
+            | ErrorDetail::ClientInternal(_)
+    )
+}
+
+/// Health of an RPC endpoint, as last seen by a probe or a query
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EndpointHealth {
+    pub(crate) healthy: bool,
+    /// Last committed block height reported by the endpoint
+    pub(crate) height: Option<BlockHeight>,
+    /// Error of the last failed probe or query
+    pub(crate) error: Option<String>,
+}
+
+/// An RPC endpoint of the client
+pub(crate) struct Endpoint {
+    pub(crate) url: String,
+    priority: u32,
+    weight: u32,
+    client: HttpClient,
+    pub(crate) health: Mutex<EndpointHealth>,
+}
+
+impl Endpoint {
+    fn health(&self) -> EndpointHealth {
+        self.health.lock().unwrap().clone()
+    }
+
+    fn set_health(&self, healthy: bool, height: Option<BlockHeight>, error: Option<String>) {
+        let mut health = self.health.lock().unwrap();
+        health.healthy = healthy;
+        health.height = height.or(health.height);
+        health.error = error;
+    }
+}
+
+/// Status of an RPC endpoint, as reported by the RPC health check
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStatus {
+    pub url: String,
+    pub priority: u32,
+    pub weight: u32,
+    pub healthy: bool,
+    pub height: Option<u64>,
+    /// Number of blocks behind the highest endpoint
+    pub lag: Option<u64>,
+    pub lagging: bool,
+    pub error: Option<String>,
+}
+
+/// Client of a Namada node. Slowly changing query results are kept in a
+/// [`QueryCache`].
+///
+/// The client may be given several RPC endpoints. Queries go to a healthy
+/// endpoint of the lowest priority, spread among those in proportion to their
+/// weight, and fail over to the next endpoints when it can't be reached. Endpoints that
+/// are unhealthy, or lag more than `max_lag_blocks` behind the highest one,
+/// are only tried last.
 pub struct NamadaClient {
-    rpc_client: HttpClient,
-    rpc_url: String,
+    pub(crate) endpoints: Vec<Endpoint>,
+    max_lag_blocks: u64,
+    /// Number of queries sent, used to spread them by weight
+    rotation: AtomicU64,
+    cache: QueryCache,
 }
 
 impl NamadaClient {
-    pub async fn new(rpc_url: String) -> Result<Self, ClientError> {
-        info!("Initializing Namada client with RPC URL: {}", rpc_url);
-        let url = Url::from_str(&rpc_url)
-            .map_err(|e| ClientError::InvalidUrl(e.to_string()))?;
-        let rpc_client = HttpClient::new(url)
-            .map_err(|e| ClientError::ConnectionError(e.to_string()))?;
-        Ok(Self { rpc_client, rpc_url })
+    pub async fn with_endpoints(endpoints: Vec<RpcEndpointConfig>, max_lag_blocks: u64) -> Result<Self, ClientError> {
+        let endpoints = endpoints.into_iter()
+            .map(|endpoint| {
+                info!("Initializing Namada client with RPC URL: {} (priority {}, weight {})", endpoint.url, endpoint.priority, endpoint.weight);
+                let url = Url::from_str(&endpoint.url)
+                    .map_err(ClientError::InvalidUrl)?;
+                let client = HttpClient::new(url)
+                    .map_err(ClientError::ConnectionError)?;
+                Ok(Endpoint {
+                    url: endpoint.url,
+                    priority: endpoint.priority,
+                    weight: endpoint.weight.max(1),
+                    client,
+                    // Endpoints are presumed healthy until probed
+                    health: Mutex::new(EndpointHealth { healthy: true, ..Default::default() }),
+                })
+            })
+            .collect::<Result<Vec<_>, ClientError>>()?;
+        if endpoints.is_empty() {
+            return Err(ClientError::QueryError("No RPC endpoint configured".to_string()));
+        }
+        Ok(Self { endpoints, max_lag_blocks, rotation: AtomicU64::new(0), cache: QueryCache::default() })
     }
 
+    /// Probe every endpoint, recording its health and last block height.
+    /// Succeeds if at least one endpoint is healthy.
     pub async fn check_connection(&self) -> Result<(), ClientError> {
-        // Try a simple health check (e.g., get epoch)
-        self.query_epoch().await.map(|_| ())
+        let probes = self.endpoints.iter().map(|endpoint| async move {
+            let result = rpc::query_block(&endpoint.client).await;
+            match &result {
+                Ok(block) => endpoint.set_health(true, block.as_ref().map(|block| block.height), None),
+                Err(err) => {
+                    warn!("RPC endpoint {} failed its health probe: {}", endpoint.url, err);
+                    endpoint.set_health(false, None, Some(err.to_string()));
+                }
+            }
+            result
+        });
+        let results = futures::future::join_all(probes).await;
+        
+        let mut last_err = None;
+        for result in results {
+            match result {
+                Ok(_) => return Ok(()),
+                Err(err) => last_err = Some(err),
+            }
+        }
+        Err(last_err.map(ClientError::from).unwrap_or_else(|| ClientError::QueryError("No RPC endpoint configured".to_string())))
     }
 
+    /// Probe the endpoints every `interval`, until the client is dropped
+    pub fn spawn_health_probe(self: &Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let client = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                ticker.tick().await;
+                let Some(client) = client.upgrade() else { break };
+                if let Err(err) = client.check_connection().await {
+                    warn!("No RPC endpoint is healthy: {}", err);
+                }
+            }
+        })
+    }
+
+    /// URL of the endpoint the next query will be sent to first
     pub fn rpc_url(&self) -> &str {
-        &self.rpc_url
+        &self.candidates_at(self.rotation.load(Ordering::Relaxed))[0].url
     }
 
-    pub async fn query_epoch(&self) -> Result<Epoch, ClientError> {
-        let client = self.rpc_client.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::query_epoch(&client).await
+    /// Health of every endpoint, in configuration order
+    pub fn endpoint_statuses(&self) -> Vec<EndpointStatus> {
+        let healths: Vec<_> = self.endpoints.iter().map(Endpoint::health).collect();
+        let highest = self.highest_height(&healths);
+        self.endpoints.iter().zip(healths)
+            .map(|(endpoint, health)| {
+                let lag = highest.zip(health.height).map(|(highest, height)| highest.0.saturating_sub(height.0));
+                EndpointStatus {
+                    url: endpoint.url.clone(),
+                    priority: endpoint.priority,
+                    weight: endpoint.weight,
+                    healthy: health.healthy,
+                    height: health.height.map(|height| height.0),
+                    lag,
+                    lagging: lag.is_some_and(|lag| lag > self.max_lag_blocks),
+                    error: health.error,
+                }
             })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+            .collect()
     }
 
-    pub async fn get_liveness_info(&self) -> Result<LivenessInfo, ClientError> {
-        let client = self.rpc_client.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::get_validators_liveness_info(&client).await
+    /// Highest block height reported by a healthy endpoint, as last probed
+    pub fn last_height(&self) -> Option<BlockHeight> {
+        let healths: Vec<_> = self.endpoints.iter().map(Endpoint::health).collect();
+        self.highest_height(&healths)
+    }
+
+    /// Highest block height reported by a healthy endpoint
+    fn highest_height(&self, healths: &[EndpointHealth]) -> Option<BlockHeight> {
+        healths.iter()
+            .filter(|health| health.healthy)
+            .filter_map(|health| health.height)
+            .max()
+    }
+
+    /// The endpoints in the order a query should try them, advancing the
+    /// rotation for the next query
+    pub(crate) fn candidates(&self) -> Vec<&Endpoint> {
+        self.candidates_at(self.rotation.fetch_add(1, Ordering::Relaxed))
+    }
+
+    /// The endpoints in the order the query number `rotation` should try them:
+    /// usable endpoints by priority, the preferred ones rotated by weight, then
+    /// the others
+    fn candidates_at(&self, rotation: u64) -> Vec<&Endpoint> {
+        let healths: Vec<_> = self.endpoints.iter().map(Endpoint::health).collect();
+        let highest = self.highest_height(&healths);
+        let usable: Vec<bool> = healths.iter()
+            .map(|health| {
+                let lagging = highest.zip(health.height)
+                    .is_some_and(|(highest, height)| highest.0.saturating_sub(height.0) > self.max_lag_blocks);
+                health.healthy && !lagging
             })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+            .collect();
+        
+        let mut order: Vec<usize> = (0..self.endpoints.len()).collect();
+        order.sort_by_key(|&i| (!usable[i], self.endpoints[i].priority));
+        
+        // Weighted round robin among the usable endpoints of the lowest priority
+        let preferred = order.iter()
+            .take_while(|&&i| usable[i] && self.endpoints[i].priority == self.endpoints[order[0]].priority)
+            .count();
+        let total_weight: u64 = order[..preferred].iter().map(|&i| self.endpoints[i].weight as u64).sum();
+        if total_weight > 0 {
+            let mut ticket = rotation % total_weight;
+            let first = order[..preferred].iter()
+                .position(|&i| {
+                    let weight = self.endpoints[i].weight as u64;
+                    let hit = ticket < weight;
+                    ticket -= weight.min(ticket);
+                    hit
+                })
+                .unwrap_or(0);
+            order[..preferred].rotate_left(first);
+        }
+        
+        order.into_iter().map(|i| &self.endpoints[i]).collect()
+    }
+
+    /// Run a query against the candidate endpoints until one answers.
+    /// Endpoints are only failed over when they can't be reached; a query the
+    /// node answers with an error fails the same way on every endpoint.
+    /// Endpoints that failed before a later one answered are marked unhealthy
+    /// until the next probe.
+    ///
+    /// The call is recorded in the RPC metrics under `method`, and runs in an
+    /// `rpc_call` span, a child of the span of the request being served.
+    async fn query<'a, T, F, Fut>(&'a self, method: &'static str, query: F) -> Result<T, ClientError>
+    where
+        F: Fn(&'a HttpClient) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>> + 'a,
+    {
+        let span = info_span!("rpc_call", method, endpoint = Empty, success = Empty, duration_ms = Empty);
+        async move {
+            let started_at = Instant::now();
+            let result = self.query_endpoints(query).await;
+            let duration = started_at.elapsed();
+            let span = Span::current();
+            span.record("success", result.is_ok());
+            span.record("duration_ms", duration.as_millis() as u64);
+            METRICS.record_rpc_call(method, duration, result.is_ok());
+            result
+        }.instrument(span).await
+    }
+
+    async fn query_endpoints<'a, T, F, Fut>(&'a self, query: F) -> Result<T, ClientError>
+    where
+        F: Fn(&'a HttpClient) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>> + 'a,
+    {
+        let mut failed: Vec<(&Endpoint, String)> = Vec::new();
+        let mut last_err = None;
+        for endpoint in self.candidates() {
+            let result = query(&endpoint.client).await;
+            match result {
+                // Only errors of the connection are worth retrying on another endpoint
+                Err(err) if err.is_connection_error() => {
+                    failed.push((endpoint, err.to_string()));
+                    last_err = Some(err);
+                }
+                result => {
+                    Span::current().record("endpoint", endpoint.url.as_str());
+                    for (endpoint, err) in failed {
+                        warn!("Failed over from RPC endpoint {}: {}", endpoint.url, err);
+                        endpoint.set_health(false, None, Some(err));
+                    }
+                    return result;
+                }
+            }
+        }
+        Err(last_err.expect("the client has at least one endpoint"))
+    }
+
+    /// Hit and miss statistics of the query cache
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache.stats()
+    }
+
+    pub async fn query_epoch(&self) -> Result<Epoch, ClientError> {
+        let epoch = self.query("query_epoch", |client| async move {
+            Ok(rpc::query_epoch(client).await?)
+        }).await?;
+        self.cache.observe_epoch(epoch);
+        Ok(epoch)
+    }
+
+    /// Get the height of the last committed block
+    pub async fn query_last_height(&self) -> Result<BlockHeight, ClientError> {
+        self.query("query_last_height", |client| async move {
+            Ok(rpc::query_block(client).await?)
+        }).await?
+            .map(|block| block.height)
+            .ok_or_else(|| ClientError::QueryError("No block has been committed yet".to_string()))
+    }
+
+    /// Get the epoch of a block height, or `None` if the node doesn't know it
+    pub async fn query_epoch_at_height(&self, height: BlockHeight) -> Result<Option<Epoch>, ClientError> {
+        // Unknown heights are reported with `Err(None)`, so that they are not cached
+        let epoch = self.cache.get_or_fetch(&self.cache.epoch_at_height, height, || async {
+            self.query("query_epoch_at_height", |client| async move {
+                Ok(rpc::query_epoch_at_height(client, height).await?)
+            }).await
+                .map_err(Some)?
+                .ok_or(None)
+        }).await;
+        match epoch {
+            Ok(epoch) => {
+                self.cache.observe_epoch(epoch);
+                Ok(Some(epoch))
+            }
+            Err(None) => Ok(None),
+            Err(Some(err)) => Err(err),
+        }
+    }
+
+    /// Get the PoS parameters
+    pub async fn get_pos_params(&self) -> Result<PosParams, ClientError> {
+        self.cache.get_or_fetch(&self.cache.pos_params, (), || self.query("get_pos_params", |client| async move {
+            Ok(rpc::get_pos_params(client).await?)
+        })).await
+    }
+
+    pub async fn get_liveness_info(&self) -> Result<LivenessInfo, ClientError> {
+        self.query("get_liveness_info", |client| async move {
+            Ok(rpc::get_validators_liveness_info(client).await?)
+        }).await
     }
 
     pub async fn get_all_validators(&self, epoch: Option<Epoch>) -> Result<Vec<Address>, ClientError> {
@@ -73,68 +417,33 @@ impl NamadaClient {
             Some(e) => e,
             None => self.query_epoch().await?,
         };
-        let client = self.rpc_client.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::get_all_validators(&client, epoch).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
-        .map(|set| set.into_iter().collect())
+        self.cache.get_or_fetch(&self.cache.validators, epoch, || self.query("get_all_validators", |client| async move {
+            Ok(rpc::get_all_validators(client, epoch).await?.into_iter().collect())
+        })).await
     }
 
     pub async fn is_validator(&self, address: &Address) -> Result<bool, ClientError> {
-        let client = self.rpc_client.clone();
-        let address = address.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::is_validator(&client, &address).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+        self.query("is_validator", |client| async move {
+            Ok(rpc::is_validator(client, address).await?)
+        }).await
     }
 
     pub async fn get_validator_state(&self, address: &Address, epoch: Option<Epoch>) -> Result<ValidatorStateInfo, ClientError> {
-        let client = self.rpc_client.clone();
-        let address = address.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::get_validator_state(&client, &address, epoch).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+        self.query("get_validator_state", |client| async move {
+            Ok(rpc::get_validator_state(client, address, epoch).await?)
+        }).await
     }
 
     pub async fn get_validator_stake(&self, epoch: Epoch, address: &Address) -> Result<namada_core::token::Amount, ClientError> {
-        let client = self.rpc_client.clone();
-        let address = address.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::get_validator_stake(&client, epoch, &address).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+        self.query("get_validator_stake", |client| async move {
+            Ok(rpc::get_validator_stake(client, epoch, address).await?)
+        }).await
     }
 
     pub async fn query_metadata(&self, address: &Address, epoch: Option<Epoch>) -> Result<(Option<ValidatorMetaData>, CommissionPair), ClientError> {
-        let client = self.rpc_client.clone();
-        let address = address.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::query_metadata(&client, &address, epoch).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+        self.cache.get_or_fetch(&self.cache.metadata, (address.clone(), epoch), || self.query("query_metadata", |client| async move {
+            Ok(rpc::query_metadata(client, address, epoch).await?)
+        })).await
     }
 
     pub async fn get_delegation_validators(&self, address: &Address, epoch: Option<Epoch>) -> Result<Vec<Address>, ClientError> {
@@ -142,17 +451,45 @@ impl NamadaClient {
             Some(e) => e,
             None => self.query_epoch().await?,
         };
-        let client = self.rpc_client.clone();
-        let address = address.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::get_delegation_validators(&client, &address, epoch).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
-        .map(|set| set.into_iter().collect())
+        self.query("get_delegation_validators", |client| async move {
+            Ok(rpc::get_delegation_validators(client, address, epoch).await?.into_iter().collect())
+        }).await
+    }
+
+    /// Get the validators an address delegates to, with the bonded amount at each
+    pub async fn get_delegations(&self, address: &Address, epoch: Option<Epoch>) -> Result<Vec<(Address, namada_core::token::Amount)>, ClientError> {
+        let epoch = match epoch {
+            Some(e) => e,
+            None => self.query_epoch().await?,
+        };
+        self.query("get_delegations", |client| async move {
+            Ok(rpc::get_delegations_of_delegator_at(client, address, epoch).await?.into_iter().collect())
+        }).await
+    }
+
+    /// Get unclaimed rewards of a bond. Without a source, returns the validator's self-bond rewards
+    pub async fn query_rewards(&self, validator: &Address, source: Option<&Address>, epoch: Option<Epoch>) -> Result<namada_core::token::Amount, ClientError> {
+        self.query("query_rewards", |client| async move {
+            Ok(rpc::query_rewards(client, &source.cloned(), validator, &epoch).await?)
+        }).await
+    }
+
+    /// Get the total bonded stake at an epoch
+    pub async fn get_total_stake(&self, epoch: Option<Epoch>) -> Result<namada_core::token::Amount, ClientError> {
+        let epoch = match epoch {
+            Some(e) => e,
+            None => self.query_epoch().await?,
+        };
+        self.query("get_total_stake", |client| async move {
+            Ok(rpc::get_total_staked_tokens(client, epoch).await?)
+        }).await
+    }
+
+    /// Get bonds and unbonds with their slashes, optionally filtered by source and validator
+    pub async fn get_bonds_and_unbonds(&self, source: Option<&Address>, validator: Option<&Address>) -> Result<BondsAndUnbondsDetails, ClientError> {
+        self.query("get_bonds_and_unbonds", |client| async move {
+            Ok(rpc::bonds_and_unbonds(client, &source.cloned(), &validator.cloned()).await?)
+        }).await
     }
 
     pub async fn get_consensus_validator_set(&self, epoch: Option<Epoch>) -> Result<Vec<namada_proof_of_stake::types::WeightedValidator>, ClientError> {
@@ -160,16 +497,9 @@ impl NamadaClient {
             Some(e) => e,
             None => self.query_epoch().await?,
         };
-        let client = self.rpc_client.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                RPC.vp().pos().consensus_validator_set(&client, &Some(epoch)).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
-        .map(|set| set.into_iter().collect())
+        self.cache.get_or_fetch(&self.cache.validator_sets, (ValidatorSetKind::Consensus, epoch), || self.query("get_consensus_validator_set", |client| async move {
+            Ok(RPC.vp().pos().consensus_validator_set(client, &Some(epoch)).await?.into_iter().collect())
+        })).await
     }
 
     pub async fn get_below_capacity_validator_set(&self, epoch: Option<Epoch>) -> Result<Vec<namada_proof_of_stake::types::WeightedValidator>, ClientError> {
@@ -177,71 +507,174 @@ impl NamadaClient {
             Some(e) => e,
             None => self.query_epoch().await?,
         };
-        let client = self.rpc_client.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                RPC.vp().pos().below_capacity_validator_set(&client, &Some(epoch)).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
-        .map(|set| set.into_iter().collect())
+        self.cache.get_or_fetch(&self.cache.validator_sets, (ValidatorSetKind::BelowCapacity, epoch), || self.query("get_below_capacity_validator_set", |client| async move {
+            Ok(RPC.vp().pos().below_capacity_validator_set(client, &Some(epoch)).await?.into_iter().collect())
+        })).await
+    }
+
+    /// Get the slashes applied to a validator
+    pub async fn get_validator_slashes(&self, address: &Address) -> Result<Vec<Slash>, ClientError> {
+        self.query("get_validator_slashes", |client| async move {
+            Ok(RPC.vp().pos().validator_slashes(client, address).await?)
+        }).await
+    }
+
+    /// Get the slashes applied to every validator
+    pub async fn get_all_slashes(&self) -> Result<Vec<(Address, Vec<Slash>)>, ClientError> {
+        self.query("get_all_slashes", |client| async move {
+            Ok(RPC.vp().pos().slashes(client).await?.into_iter().collect())
+        }).await
+    }
+
+    /// Get the epoch of a validator's last infraction, if any
+    pub async fn query_last_infraction_epoch(&self, address: &Address) -> Result<Option<Epoch>, ClientError> {
+        self.query("query_last_infraction_epoch", |client| async move {
+            Ok(rpc::query_last_infraction_epoch(client, address).await?)
+        }).await
     }
 
     pub async fn validator_by_tm_addr(&self, tm_addr: String) -> Result<Option<Address>, ClientError> {
-        let client = self.rpc_client.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                RPC.vp().pos().validator_by_tm_addr(&client, &tm_addr).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+        let tm_addr = &tm_addr;
+        self.query("validator_by_tm_addr", |client| async move {
+            Ok(RPC.vp().pos().validator_by_tm_addr(client, tm_addr).await?)
+        }).await
     }
 
     // Token-related methods
     
     /// Get token balance for a specific owner
     pub async fn get_token_balance(&self, token: &Address, owner: &Address, height: Option<BlockHeight>) -> Result<namada_core::token::Amount, ClientError> {
-        let client = self.rpc_client.clone();
-        let token = token.clone();
-        let owner = owner.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::get_token_balance(&client, &token, &owner, height).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
-    }
-
-    /// Get total supply of a token
-    pub async fn get_token_total_supply(&self, token: &Address) -> Result<namada_core::token::Amount, ClientError> {
-        let client = self.rpc_client.clone();
-        let token = token.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::get_token_total_supply(&client, &token).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+        let Some(height) = height else {
+            return self.query("get_token_balance", |client| async move {
+                Ok(rpc::get_token_balance(client, token, owner, None).await?)
+            }).await;
+        };
+        self.cache.get_or_fetch(&self.cache.balances, (token.clone(), owner.clone(), height), || self.query("get_token_balance", |client| async move {
+            Ok(rpc::get_token_balance(client, token, owner, Some(height)).await?)
+        })).await
+    }
+
+    /// Get total supply of a token, optionally at a given height
+    ///
+    /// The total supply query of the node only serves the latest height, so
+    /// pinned queries read the minted balance from storage instead.
+    pub async fn get_token_total_supply(&self, token: &Address, height: Option<BlockHeight>) -> Result<namada_core::token::Amount, ClientError> {
+        let Some(height) = height else {
+            return self.query("get_token_total_supply", |client| async move {
+                Ok(rpc::get_token_total_supply(client, token).await?)
+            }).await;
+        };
+        self.cache.get_or_fetch(&self.cache.total_supply, (token.clone(), height), || self.query("get_token_total_supply", |client| async move {
+            let key = minted_balance_key(token);
+            let (value, _proof) = rpc::query_storage_value_bytes(client, &key, Some(height), false).await?;
+            value
+                .map(|bytes| namada_core::token::Amount::try_from_slice(&bytes))
+                .transpose()
+                .map(Option::unwrap_or_default)
+                .map_err(ClientError::from)
+        })).await
     }
 
     /// Get the native token address
     pub async fn query_native_token(&self) -> Result<Address, ClientError> {
-        let client = self.rpc_client.clone();
-        spawn_blocking(move || {
-            tokio::runtime::Handle::current().block_on(async {
-                rpc::query_native_token(&client).await
-            })
-        })
-        .await
-        .map_err(|e| ClientError::QueryError(e.to_string()))?
-        .map_err(|e| ClientError::QueryError(e.to_string()))
+        self.cache.get_or_fetch(&self.cache.native_token, (), || self.query("query_native_token", |client| async move {
+            Ok(rpc::query_native_token(client).await?)
+        })).await
+    }
+
+    // Governance-related methods
+
+    /// Get the governance parameters
+    pub async fn get_gov_params(&self) -> Result<GovernanceParameters, ClientError> {
+        self.cache.get_or_fetch(&self.cache.gov_params, (), || self.query("get_gov_params", |client| async move {
+            Ok(RPC.vp().gov().parameters(client).await?)
+        })).await
+    }
+
+    /// Get the number of proposals submitted, which is also the ID of the next one
+    pub async fn get_proposal_counter(&self) -> Result<u64, ClientError> {
+        self.query("get_proposal_counter", |client| async move {
+            Ok(rpc::query_storage_value::<_, u64>(client, &gov_storage::get_counter_key()).await?)
+        }).await
     }
-} 
\ No newline at end of file
+
+    /// Get a governance proposal
+    pub async fn get_proposal(&self, id: u64) -> Result<Option<StorageProposal>, ClientError> {
+        // Unknown proposals are reported with `Err(None)`, so that they are not cached
+        let proposal = self.cache.get_or_fetch(&self.cache.proposals, id, || async {
+            self.query("get_proposal", |client| async move {
+                Ok(rpc::query_proposal_by_id(client, id).await?)
+            }).await
+                .map_err(Some)?
+                .ok_or(None)
+        }).await;
+        match proposal {
+            Ok(proposal) => Ok(Some(proposal)),
+            Err(None) => Ok(None),
+            Err(Some(err)) => Err(err),
+        }
+    }
+
+    /// Get the tally of a proposal: the stored result once voting has ended,
+    /// the current votes while voting. `None` before voting starts.
+    pub async fn get_proposal_result(&self, id: u64) -> Result<Option<ProposalResult>, ClientError> {
+        let result = self.cache.get_or_fetch(&self.cache.proposal_results, id, || async {
+            self.query("get_proposal_result", |client| async move {
+                Ok(rpc::query_proposal_result(client, id).await?)
+            }).await
+                .map_err(Some)?
+                .ok_or(None)
+        }).await;
+        match result {
+            Ok(result) => Ok(Some(result)),
+            Err(None) => Ok(None),
+            Err(Some(err)) => Err(err),
+        }
+    }
+
+    /// Get the votes cast on a proposal
+    pub async fn get_proposal_votes(&self, id: u64) -> Result<Vec<Vote>, ClientError> {
+        self.query("get_proposal_votes", |client| async move {
+            Ok(rpc::query_proposal_votes(client, id).await?)
+        }).await
+    }
+
+    // Chain-related methods
+
+    /// Get a block from the CometBFT RPC
+    pub async fn get_block(&self, height: BlockHeight) -> Result<block::Response, ClientError> {
+        let height = namada_core::tendermint::block::Height::try_from(height)
+            .map_err(|e| ClientError::QueryError(e.to_string()))?;
+        self.query("get_block", |client| async move {
+            client.block(height).await.map_err(ClientError::CometBft)
+        }).await
+    }
+
+    /// Get the commit of a block, with the signature of each validator, from
+    /// the CometBFT RPC
+    pub async fn get_commit(&self, height: BlockHeight) -> Result<commit::Response, ClientError> {
+        let height = namada_core::tendermint::block::Height::try_from(height)
+            .map_err(|e| ClientError::QueryError(e.to_string()))?;
+        self.query("get_commit", |client| async move {
+            client.commit(height).await.map_err(ClientError::CometBft)
+        }).await
+    }
+
+    /// Get the CometBFT validator set of a block, with the voting power of
+    /// each validator
+    pub async fn get_comet_validators(&self, height: BlockHeight) -> Result<Vec<namada_core::tendermint::validator::Info>, ClientError> {
+        let height = namada_core::tendermint::block::Height::try_from(height)
+            .map_err(|e| ClientError::QueryError(e.to_string()))?;
+        self.query("get_comet_validators", |client| async move {
+            Ok(client.validators(height, Paging::All).await.map_err(ClientError::CometBft)?.validators)
+        }).await
+    }
+
+    /// Get the events of an applied transaction, or `None` if the node has no
+    /// record of it. The node only keeps the events of recent blocks.
+    pub async fn get_tx_events(&self, hash: &Hash) -> Result<Option<TxAppliedEvents>, ClientError> {
+        self.query("get_tx_events", |client| async move {
+            Ok(RPC.shell().applied(client, hash).await?)
+        }).await
+    }
+}
@@ -1,32 +1,293 @@
 use clap::Parser;
+use ipnet::IpNet;
+use namada_core::address::Address;
 use serde::Deserialize;
+use std::net::IpAddr;
 use std::path::PathBuf;
+use std::str::FromStr;
 use tracing::{info, warn};
+use warp::http::header::HeaderName;
+use warp::http::Method;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct CliArgs {
-    /// Namada RPC URL
+    /// Namada RPC URLs, comma-separated, each as `URL` or `URL|PRIORITY|WEIGHT`
     #[arg(short, long, env = "NAMADA_RPC_URL")]
     pub rpc_url: Option<String>,
 
     /// API server port
-    #[arg(short, long, env = "API_PORT", default_value = "3000")]
-    pub port: u16,
+    #[arg(short, long, env = "API_PORT")]
+    pub port: Option<u16>,
+
+    /// IPv4 or IPv6 address the API server listens on
+    #[arg(long)]
+    pub bind_address: Option<String>,
+
+    /// TLS certificate chain file (PEM), enables HTTPS together with the key
+    #[arg(long)]
+    pub tls_cert_path: Option<PathBuf>,
+
+    /// TLS private key file (PEM)
+    #[arg(long)]
+    pub tls_key_path: Option<PathBuf>,
 
-    /// Config file path
+    /// Time given to in-flight requests to complete on shutdown, in milliseconds
+    #[arg(long)]
+    pub shutdown_timeout_ms: Option<u64>,
+
+    /// Config file path (TOML or YAML)
     #[arg(short, long)]
     pub config: Option<PathBuf>,
+
+    /// Maximum number of validators queried concurrently by validators_details and total_rewards
+    #[arg(long)]
+    pub validators_details_concurrency: Option<usize>,
+
+    /// Deadline of a validators_details request, in milliseconds
+    #[arg(long)]
+    pub validators_details_timeout_ms: Option<u64>,
+
+    /// Interval between two health probes of the RPC endpoints, in milliseconds
+    #[arg(long)]
+    pub rpc_health_interval_ms: Option<u64>,
+
+    /// Number of blocks an RPC endpoint may lag behind the highest one before it is avoided
+    #[arg(long)]
+    pub rpc_max_lag_blocks: Option<u64>,
+
+    /// Log format: text, json or pretty
+    #[arg(long)]
+    pub log_format: Option<String>,
+
+    /// OTLP/HTTP collector URL the traces are exported to, e.g. http://localhost:4318
+    #[arg(long)]
+    pub otlp_endpoint: Option<String>,
+
+    /// Enable the rate limiting of requests
+    #[arg(long)]
+    pub rate_limit_enabled: Option<bool>,
+
+    /// Interval between two polls of the chain events sent to the WebSocket clients, in milliseconds
+    #[arg(long)]
+    pub events_poll_interval_ms: Option<u64>,
+
+    /// Enable the monitor of the uptime of validators
+    #[arg(long)]
+    pub uptime_monitor_enabled: Option<bool>,
+}
+
+/// Token bucket quota: `burst` requests at once, refilled at `requests_per_second`
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RateQuota {
+    pub requests_per_second: f64,
+    pub burst: u32,
+}
+
+/// Quotas of each route class. Routes of a class without a quota are not limited.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct RateLimitsConfig {
+    /// Routes not in another class
+    pub standard: Option<RateQuota>,
+    /// Routes querying many validators at once
+    pub heavy: Option<RateQuota>,
+    /// Health, metrics and documentation routes
+    pub health: Option<RateQuota>,
+}
+
+/// An API key, sent in the `X-API-Key` header. Requests with a key are limited
+/// per key instead of per IP address, with the quotas of the key, falling back
+/// to the per-IP quotas for the route classes it doesn't set.
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct ApiKeyConfig {
+    pub name: String,
+    pub key: String,
+    #[serde(default)]
+    pub rate_limits: RateLimitsConfig,
+}
+
+/// Longest window the uptime of validators can be computed over, in blocks.
+/// The signatures of a whole window are fetched when the monitor starts.
+pub const MAX_UPTIME_WINDOW: u64 = 10_000;
+
+/// Monitor of the uptime of validators, alerting webhooks when the uptime of
+/// a watched validator falls close to the liveness threshold
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct UptimeMonitorConfig {
+    pub enabled: bool,
+    /// Interval between two polls of the new blocks, in milliseconds
+    pub poll_interval_ms: u64,
+    /// Windows the uptime is computed over, in blocks
+    pub windows: Vec<u64>,
+    /// Watched validators, every consensus validator when empty
+    pub validators: Vec<Address>,
+    /// Validators are at risk when their uptime over a window falls below
+    /// `liveness_threshold + alert_margin`
+    pub alert_margin: f64,
+    /// URLs the alerts are POSTed to as JSON
+    pub webhooks: Vec<String>,
+    pub webhook_timeout_ms: u64,
+}
+
+/// Format of the log lines
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human readable, one line per event
+    Text,
+    /// One JSON object per event, with the fields of its spans
+    Json,
+    /// Human readable, over several lines
+    Pretty,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+/// An RPC endpoint. Endpoints with the lowest priority are preferred, and
+/// requests are spread among them in proportion to their weight.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct RpcEndpointConfig {
+    pub url: String,
+    #[serde(default)]
+    pub priority: u32,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+impl RpcEndpointConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if !is_valid_url(&self.url) {
+            return Err(ConfigError::InvalidRpcUrl(self.url.clone()));
+        }
+        if self.weight == 0 {
+            return Err(ConfigError::InvalidValue(format!("Weight of RPC endpoint '{}' must be greater than 0", self.url)));
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RpcEndpointConfig {
+    type Err = ConfigError;
+
+    /// Parse `URL` or `URL|PRIORITY|WEIGHT`. Priority defaults to 0 and weight to 1.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split('|');
+        let url = parts.next().unwrap_or_default().to_string();
+        let mut number = |name: &str, default: u32| match parts.next() {
+            Some(part) => part.trim().parse::<u32>()
+                .map_err(|_| ConfigError::InvalidValue(format!("Invalid {} of RPC endpoint '{}'", name, s))),
+            None => Ok(default),
+        };
+        let priority = number("priority", 0)?;
+        let weight = number("weight", 1)?;
+        if parts.next().is_some() {
+            return Err(ConfigError::InvalidValue(format!("RPC endpoint '{}' has more than three fields", s)));
+        }
+        
+        let endpoint = Self { url, priority, weight };
+        endpoint.validate()?;
+        Ok(endpoint)
+    }
+}
+
+/// Parse a comma-separated list of RPC endpoints
+pub fn parse_rpc_endpoints(s: &str) -> Result<Vec<RpcEndpointConfig>, ConfigError> {
+    let endpoints = s.split(',')
+        .filter(|endpoint| !endpoint.trim().is_empty())
+        .map(RpcEndpointConfig::from_str)
+        .collect::<Result<Vec<_>, _>>()?;
+    if endpoints.is_empty() {
+        return Err(ConfigError::InvalidValue("At least one RPC endpoint is required".to_string()));
+    }
+    Ok(endpoints)
+}
+
+#[derive(Debug, Clone)]
 pub struct Config {
-    pub rpc_url: String,
+    pub rpc_endpoints: Vec<RpcEndpointConfig>,
+    pub bind_address: IpAddr,
     pub port: u16,
+    pub tls_cert_path: Option<PathBuf>,
+    pub tls_key_path: Option<PathBuf>,
+    pub shutdown_timeout_ms: u64,
     pub cors_allowed_origins: Vec<String>,
+    pub cors_allowed_methods: Vec<String>,
+    pub cors_allowed_headers: Vec<String>,
+    pub validators_details_concurrency: usize,
+    pub validators_details_timeout_ms: u64,
+    pub rpc_health_interval_ms: u64,
+    pub rpc_max_lag_blocks: u64,
+    pub events_poll_interval_ms: u64,
+    pub log_format: LogFormat,
+    pub otlp_endpoint: Option<String>,
+    pub rate_limit_enabled: bool,
+    /// Proxies whose forwarded client addresses are trusted
+    pub trusted_proxies: Vec<IpNet>,
+    /// Per-IP quotas
+    pub rate_limits: RateLimitsConfig,
+    pub api_keys: Vec<ApiKeyConfig>,
+    pub uptime_monitor: UptimeMonitorConfig,
+}
+
+/// Configuration merged from its layers, before validation
+///
+/// The RPC endpoints are either given as a table list in the config file, or
+/// as a `rpc_url` string in the `URL|PRIORITY|WEIGHT` list format, which wins.
+#[derive(Deserialize, Debug)]
+struct RawConfig {
+    rpc_url: Option<String>,
+    rpc_endpoints: Option<Vec<RpcEndpointConfig>>,
+    bind_address: String,
+    port: u16,
+    tls_cert_path: Option<PathBuf>,
+    tls_key_path: Option<PathBuf>,
+    shutdown_timeout_ms: u64,
+    cors_allowed_origins: Vec<String>,
+    cors_allowed_methods: Vec<String>,
+    cors_allowed_headers: Vec<String>,
+    validators_details_concurrency: usize,
+    validators_details_timeout_ms: u64,
+    rpc_health_interval_ms: u64,
+    rpc_max_lag_blocks: u64,
+    events_poll_interval_ms: u64,
+    log_format: LogFormat,
+    otlp_endpoint: Option<String>,
+    rate_limit_enabled: bool,
+    trusted_proxies: Vec<String>,
+    rate_limits: RateLimitsConfig,
+    #[serde(default)]
+    api_keys: Vec<ApiKeyConfig>,
+    uptime_monitor: UptimeMonitorConfig,
+}
+
+/// Read an environment variable
+fn env_var(name: &str) -> Option<String> {
+    std::env::var(name).ok()
+}
+
+/// Read a comma-separated list from an environment variable
+fn env_list(name: &str) -> Option<Vec<String>> {
+    env_var(name).map(|list| {
+        list.split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(str::to_string)
+            .collect()
+    })
+}
+
+/// Parse an IP address, or a network in CIDR notation
+fn parse_ip_network(network: &str) -> Option<IpNet> {
+    IpNet::from_str(network).ok()
+        .or_else(|| IpAddr::from_str(network).ok().map(IpNet::from))
 }
 
 impl Config {
+    /// Load the configuration. Each layer overrides the previous ones:
+    /// defaults, the config file, environment variables (and the `.env`
+    /// file), then command-line arguments.
     pub fn load(args: CliArgs) -> Result<Self, ConfigError> {
         // First load from .env file
         match dotenvy::dotenv() {
@@ -34,44 +295,240 @@ impl Config {
             Err(e) => warn!("No .env file found: {}", e),
         }
         
-        // Initialize with defaults
-        let mut config = Config {
-            rpc_url: std::env::var("NAMADA_RPC_URL")
-                .unwrap_or_else(|_| {
-                    info!("NAMADA_RPC_URL not found in environment, using default");
-                    "http://localhost:26657".to_string()
-                }),
-            port: std::env::var("API_PORT")
-                .ok()
-                .and_then(|p| p.parse::<u16>().ok())
-                .unwrap_or_else(|| {
-                    info!("API_PORT not found in environment or invalid, using default");
-                    3000
-                }),
-            cors_allowed_origins: vec!["*".to_string()],
-        };
+        // Defaults
+        let mut builder = config::Config::builder()
+            .set_default("bind_address", "127.0.0.1")?
+            .set_default("port", 3000)?
+            .set_default("shutdown_timeout_ms", 30_000)?
+            .set_default("cors_allowed_origins", vec!["*"])?
+            .set_default("cors_allowed_methods", vec!["GET"])?
+            .set_default("cors_allowed_headers", vec!["content-type", "x-api-key", "x-request-id"])?
+            .set_default("validators_details_concurrency", 10)?
+            .set_default("validators_details_timeout_ms", 10_000)?
+            .set_default("rpc_health_interval_ms", 10_000)?
+            .set_default("rpc_max_lag_blocks", 5)?
+            .set_default("events_poll_interval_ms", 2_000)?
+            .set_default("log_format", "text")?
+            .set_default("rate_limit_enabled", true)?
+            .set_default("trusted_proxies", Vec::<String>::new())?
+            .set_default("rate_limits.standard.requests_per_second", 20)?
+            .set_default("rate_limits.standard.burst", 40)?
+            .set_default("rate_limits.heavy.requests_per_second", 1)?
+            .set_default("rate_limits.heavy.burst", 5)?
+            .set_default("uptime_monitor.enabled", false)?
+            .set_default("uptime_monitor.poll_interval_ms", 6_000)?
+            .set_default("uptime_monitor.windows", vec![100, 1_000])?
+            .set_default("uptime_monitor.validators", Vec::<String>::new())?
+            .set_default("uptime_monitor.alert_margin", 0.05)?
+            .set_default("uptime_monitor.webhooks", Vec::<String>::new())?
+            .set_default("uptime_monitor.webhook_timeout_ms", 10_000)?;
+        
+        // Config file, its format is given by its extension
+        if let Some(path) = &args.config {
+            info!("Loading config file {}", path.display());
+            builder = builder.add_source(config::File::from(path.as_path()));
+        }
+        
+        // Environment variables
+        builder = builder
+            .set_override_option("rpc_url", env_var("NAMADA_RPC_URL"))?
+            .set_override_option("bind_address", env_var("API_BIND_ADDRESS"))?
+            .set_override_option("port", env_var("API_PORT"))?
+            .set_override_option("tls_cert_path", env_var("API_TLS_CERT_PATH"))?
+            .set_override_option("tls_key_path", env_var("API_TLS_KEY_PATH"))?
+            .set_override_option("shutdown_timeout_ms", env_var("SHUTDOWN_TIMEOUT_MS"))?
+            .set_override_option("cors_allowed_origins", env_list("CORS_ALLOWED_ORIGINS"))?
+            .set_override_option("cors_allowed_methods", env_list("CORS_ALLOWED_METHODS"))?
+            .set_override_option("cors_allowed_headers", env_list("CORS_ALLOWED_HEADERS"))?
+            .set_override_option("validators_details_concurrency", env_var("VALIDATORS_DETAILS_CONCURRENCY"))?
+            .set_override_option("validators_details_timeout_ms", env_var("VALIDATORS_DETAILS_TIMEOUT_MS"))?
+            .set_override_option("rpc_health_interval_ms", env_var("RPC_HEALTH_INTERVAL_MS"))?
+            .set_override_option("rpc_max_lag_blocks", env_var("RPC_MAX_LAG_BLOCKS"))?
+            .set_override_option("events_poll_interval_ms", env_var("EVENTS_POLL_INTERVAL_MS"))?
+            .set_override_option("log_format", env_var("LOG_FORMAT"))?
+            .set_override_option("otlp_endpoint", env_var("OTEL_EXPORTER_OTLP_ENDPOINT"))?
+            .set_override_option("rate_limit_enabled", env_var("RATE_LIMIT_ENABLED"))?
+            .set_override_option("trusted_proxies", env_list("TRUSTED_PROXIES"))?
+            .set_override_option("uptime_monitor.enabled", env_var("UPTIME_MONITOR_ENABLED"))?
+            .set_override_option("uptime_monitor.validators", env_list("UPTIME_MONITOR_VALIDATORS"))?
+            .set_override_option("uptime_monitor.webhooks", env_list("UPTIME_MONITOR_WEBHOOKS"))?;
         
         // Override with CLI args
-        if let Some(rpc_url) = args.rpc_url {
-            info!("Using RPC URL from command line arguments");
-            config.rpc_url = rpc_url;
-        } else {
-            info!("Using RPC URL from environment: {}", config.rpc_url);
+        builder = builder
+            .set_override_option("rpc_url", args.rpc_url)?
+            .set_override_option("bind_address", args.bind_address)?
+            .set_override_option("port", args.port)?
+            .set_override_option("tls_cert_path", args.tls_cert_path.map(|path| path.display().to_string()))?
+            .set_override_option("tls_key_path", args.tls_key_path.map(|path| path.display().to_string()))?
+            .set_override_option("shutdown_timeout_ms", args.shutdown_timeout_ms)?
+            .set_override_option("validators_details_concurrency", args.validators_details_concurrency.map(|c| c as u64))?
+            .set_override_option("validators_details_timeout_ms", args.validators_details_timeout_ms)?
+            .set_override_option("rpc_health_interval_ms", args.rpc_health_interval_ms)?
+            .set_override_option("rpc_max_lag_blocks", args.rpc_max_lag_blocks)?
+            .set_override_option("events_poll_interval_ms", args.events_poll_interval_ms)?
+            .set_override_option("log_format", args.log_format)?
+            .set_override_option("otlp_endpoint", args.otlp_endpoint)?
+            .set_override_option("rate_limit_enabled", args.rate_limit_enabled)?
+            .set_override_option("uptime_monitor.enabled", args.uptime_monitor_enabled)?;
+        
+        let raw: RawConfig = builder.build()?.try_deserialize()?;
+        
+        let rpc_endpoints = match (raw.rpc_url, raw.rpc_endpoints) {
+            (Some(rpc_url), _) => parse_rpc_endpoints(&rpc_url)?,
+            (None, Some(endpoints)) => {
+                if endpoints.is_empty() {
+                    return Err(ConfigError::InvalidValue("At least one RPC endpoint is required".to_string()));
+                }
+                endpoints.iter().try_for_each(RpcEndpointConfig::validate)?;
+                endpoints
+            }
+            (None, None) => {
+                info!("No RPC URL configured, using default");
+                parse_rpc_endpoints("http://localhost:26657")?
+            }
+        };
+        info!("Using RPC endpoints: {:?}", rpc_endpoints);
+        
+        let bind_address = IpAddr::from_str(raw.bind_address.trim_start_matches('[').trim_end_matches(']'))
+            .map_err(|_| ConfigError::InvalidValue(format!("Invalid bind address '{}', expected an IPv4 or IPv6 address", raw.bind_address)))?;
+        
+        let trusted_proxies = raw.trusted_proxies.iter()
+            .map(|proxy| parse_ip_network(proxy)
+                .ok_or_else(|| ConfigError::InvalidValue(format!("Invalid trusted proxy '{}', expected an IP address or a CIDR network", proxy))))
+            .collect::<Result<_, _>>()?;
+        
+        let config = Config {
+            rpc_endpoints,
+            bind_address,
+            port: raw.port,
+            tls_cert_path: raw.tls_cert_path,
+            tls_key_path: raw.tls_key_path,
+            shutdown_timeout_ms: raw.shutdown_timeout_ms,
+            cors_allowed_origins: raw.cors_allowed_origins,
+            cors_allowed_methods: raw.cors_allowed_methods,
+            cors_allowed_headers: raw.cors_allowed_headers,
+            validators_details_concurrency: raw.validators_details_concurrency,
+            validators_details_timeout_ms: raw.validators_details_timeout_ms,
+            rpc_health_interval_ms: raw.rpc_health_interval_ms,
+            rpc_max_lag_blocks: raw.rpc_max_lag_blocks,
+            events_poll_interval_ms: raw.events_poll_interval_ms,
+            log_format: raw.log_format,
+            otlp_endpoint: raw.otlp_endpoint,
+            rate_limit_enabled: raw.rate_limit_enabled,
+            trusted_proxies,
+            rate_limits: raw.rate_limits,
+            api_keys: raw.api_keys,
+            uptime_monitor: raw.uptime_monitor,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.validators_details_concurrency == 0 {
+            return Err(ConfigError::InvalidValue("validators_details_concurrency must be greater than 0".to_string()));
         }
         
-        if args.port != 0 {
-            info!("Using port from command line arguments");
-            config.port = args.port;
-        } else {
-            info!("Using port from environment: {}", config.port);
+        if self.validators_details_timeout_ms == 0 {
+            return Err(ConfigError::InvalidValue("validators_details_timeout_ms must be greater than 0".to_string()));
         }
         
-        // Validate configuration
-        if !is_valid_url(&config.rpc_url) {
-            return Err(ConfigError::InvalidRpcUrl(config.rpc_url));
+        if self.tls_cert_path.is_some() != self.tls_key_path.is_some() {
+            return Err(ConfigError::InvalidValue("tls_cert_path and tls_key_path must be set together".to_string()));
         }
         
-        Ok(config)
+        if self.rpc_health_interval_ms == 0 {
+            return Err(ConfigError::InvalidValue("rpc_health_interval_ms must be greater than 0".to_string()));
+        }
+        
+        if self.events_poll_interval_ms == 0 {
+            return Err(ConfigError::InvalidValue("events_poll_interval_ms must be greater than 0".to_string()));
+        }
+        
+        if let Some(endpoint) = self.otlp_endpoint.as_ref().filter(|endpoint| !is_valid_url(endpoint)) {
+            return Err(ConfigError::InvalidValue(format!("Invalid OTLP endpoint '{}'", endpoint)));
+        }
+        
+        self.rate_limits.validate("rate_limits")?;
+        let mut keys = std::collections::HashSet::new();
+        for api_key in &self.api_keys {
+            if api_key.name.is_empty() || api_key.key.is_empty() {
+                return Err(ConfigError::InvalidValue("API keys must have a name and a key".to_string()));
+            }
+            if !keys.insert(api_key.key.as_str()) {
+                return Err(ConfigError::InvalidValue(format!("Key of API key '{}' is used by another API key", api_key.name)));
+            }
+            api_key.rate_limits.validate(&format!("rate_limits of API key '{}'", api_key.name))?;
+        }
+        
+        self.uptime_monitor.validate()?;
+        
+        if self.cors_allowed_origins.is_empty() {
+            return Err(ConfigError::InvalidValue("cors_allowed_origins must not be empty, use \"*\" to allow any origin".to_string()));
+        }
+        for origin in self.cors_allowed_origins.iter().filter(|origin| *origin != "*") {
+            let has_path = origin.splitn(4, '/').nth(3).is_some();
+            if !is_valid_url(origin) || has_path {
+                return Err(ConfigError::InvalidValue(format!("Invalid CORS origin '{}', expected scheme://host[:port]", origin)));
+            }
+        }
+        
+        for method in &self.cors_allowed_methods {
+            Method::from_bytes(method.as_bytes())
+                .map_err(|_| ConfigError::InvalidValue(format!("Invalid CORS method '{}'", method)))?;
+        }
+        
+        for header in &self.cors_allowed_headers {
+            HeaderName::from_bytes(header.as_bytes())
+                .map_err(|_| ConfigError::InvalidValue(format!("Invalid CORS header '{}'", header)))?;
+        }
+        
+        Ok(())
+    }
+
+    /// CORS filter allowing the configured origins, methods and headers, and
+    /// exposing the request ID to the frontends
+    pub fn cors(&self) -> warp::cors::Builder {
+        let cors = warp::cors()
+            .allow_methods(self.cors_allowed_methods.iter().map(String::as_str))
+            .allow_headers(self.cors_allowed_headers.iter().map(String::as_str))
+            .expose_headers([crate::telemetry::REQUEST_ID_HEADER, "retry-after"]);
+        if self.cors_allowed_origins.iter().any(|origin| origin == "*") {
+            cors.allow_any_origin()
+        } else {
+            cors.allow_origins(self.cors_allowed_origins.iter().map(String::as_str))
+        }
+    }
+}
+
+impl RateLimitsConfig {
+    fn validate(&self, name: &str) -> Result<(), ConfigError> {
+        for (class, quota) in [("standard", self.standard), ("heavy", self.heavy), ("health", self.health)] {
+            if let Some(quota) = quota {
+                if !quota.requests_per_second.is_finite() || quota.requests_per_second <= 0.0 || quota.burst == 0 {
+                    return Err(ConfigError::InvalidValue(format!("{}.{} must have a requests_per_second and a burst greater than 0", name, class)));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl UptimeMonitorConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        if self.poll_interval_ms == 0 || self.webhook_timeout_ms == 0 {
+            return Err(ConfigError::InvalidValue("uptime_monitor.poll_interval_ms and webhook_timeout_ms must be greater than 0".to_string()));
+        }
+        if self.windows.is_empty() || self.windows.iter().any(|window| *window == 0 || *window > MAX_UPTIME_WINDOW) {
+            return Err(ConfigError::InvalidValue(format!("uptime_monitor.windows must be between 1 and {} blocks", MAX_UPTIME_WINDOW)));
+        }
+        if !(0.0..1.0).contains(&self.alert_margin) {
+            return Err(ConfigError::InvalidValue("uptime_monitor.alert_margin must be between 0 and 1".to_string()));
+        }
+        if let Some(url) = self.webhooks.iter().find(|url| !is_valid_url(url)) {
+            return Err(ConfigError::InvalidValue(format!("Invalid uptime monitor webhook '{}'", url)));
+        }
+        Ok(())
     }
 }
 
@@ -79,6 +536,10 @@ impl Config {
 pub enum ConfigError {
     #[error("Invalid RPC URL: {0}")]
     InvalidRpcUrl(String),
+    #[error("Invalid configuration value: {0}")]
+    InvalidValue(String),
+    #[error("Failed to load configuration: {0}")]
+    Load(#[from] config::ConfigError),
 }
 
 fn is_valid_url(url: &str) -> bool {
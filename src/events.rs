@@ -0,0 +1,338 @@
+use std::collections::{BTreeSet, HashMap};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use futures::{SinkExt, Stream, StreamExt};
+use tokio::sync::broadcast;
+use tracing::warn;
+use warp::ws::{Message, WebSocket};
+use namada_core::address::Address;
+use namada_core::chain::{BlockHeight, Epoch};
+use namada_core::token::Amount;
+use crate::client::{ClientError, NamadaClient};
+use crate::models::block::BlockResponse;
+use crate::models::events::*;
+use crate::models::pos::WeightedValidatorResponse;
+
+/// Number of events kept for the subscribers lagging behind
+const EVENTS_CAPACITY: usize = 256;
+
+/// Maximum number of new blocks sent per poll. When the poller falls further
+/// behind, the older blocks are skipped.
+const MAX_BLOCKS_PER_POLL: u64 = 10;
+
+/// Maximum number of watched validators queried at once on a new epoch
+const VALIDATOR_QUERIES_CONCURRENCY: usize = 10;
+
+/// Chain events, broadcast to every subscriber. A single poller queries the
+/// RPC for them, whatever the number of subscribers, and only while there are
+/// subscribers.
+pub struct ChainEvents {
+    sender: broadcast::Sender<Arc<ChainEvent>>,
+    validators: broadcast::Sender<Arc<ValidatorSnapshot>>,
+    /// Number of subscribers watching each validator
+    watched: Mutex<HashMap<Address, usize>>,
+}
+
+impl Default for ChainEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChainEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENTS_CAPACITY);
+        let (validators, _) = broadcast::channel(EVENTS_CAPACITY);
+        Self { sender, validators, watched: Mutex::new(HashMap::new()) }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<Arc<ChainEvent>> {
+        self.sender.subscribe()
+    }
+
+    /// Send an event to the subscribers
+    pub fn publish(&self, event: ChainEvent) {
+        // Fails when there is no subscriber
+        self.sender.send(Arc::new(event)).ok();
+    }
+
+    /// Send the state of a validator to the subscribers watching it
+    pub fn publish_validator_snapshot(&self, snapshot: ValidatorSnapshot) {
+        // Fails when there is no subscriber
+        self.validators.send(Arc::new(snapshot)).ok();
+    }
+
+    /// State of a validator at each new epoch. The poller queries each watched
+    /// validator once per epoch, whatever the number of its subscribers.
+    pub fn watch_validator(self: &Arc<Self>, address: Address) -> impl Stream<Item = Arc<ValidatorSnapshot>> {
+        *self.watched.lock().unwrap().entry(address.clone()).or_default() += 1;
+        let watch = ValidatorWatch { events: self.clone(), address };
+        let address = watch.address.to_string();
+        event_stream(self.validators.subscribe())
+            .filter(move |snapshot| {
+                // The watch lives as long as the stream
+                let _ = &watch;
+                futures::future::ready(snapshot.details.address == address)
+            })
+    }
+
+    /// Validators watched by at least one subscriber
+    pub fn watched_validators(&self) -> Vec<Address> {
+        self.watched.lock().unwrap().keys().cloned().collect()
+    }
+
+    fn has_subscribers(&self) -> bool {
+        self.sender.receiver_count() > 0 || self.validators.receiver_count() > 0
+    }
+
+    /// Poll the chain for events in the background
+    pub fn spawn_poller(self: &Arc<Self>, client: Arc<NamadaClient>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        let events = Arc::downgrade(self);
+        tokio::spawn(async move {
+            let mut poller = ChainPoller::default();
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                let Some(events) = events.upgrade() else { break };
+                if !events.has_subscribers() {
+                    // Start over from the current state of the chain with the next subscriber
+                    poller = ChainPoller::default();
+                    continue;
+                }
+                if let Err(err) = poller.poll(&client, &events).await {
+                    warn!("Failed to poll chain events: {}", err);
+                }
+            }
+        })
+    }
+}
+
+/// Subscription of a stream to a validator, dropped with the stream
+struct ValidatorWatch {
+    events: Arc<ChainEvents>,
+    address: Address,
+}
+
+impl Drop for ValidatorWatch {
+    fn drop(&mut self) {
+        let mut watched = self.events.watched.lock().unwrap();
+        if let Some(count) = watched.get_mut(&self.address) {
+            *count -= 1;
+            if *count == 0 {
+                watched.remove(&self.address);
+            }
+        }
+    }
+}
+
+/// State of the chain last seen by the poller. Each part is first only
+/// recorded, then compared with on the next polls.
+#[derive(Default)]
+struct ChainPoller {
+    last_height: Option<u64>,
+    epoch: Option<Epoch>,
+    consensus_set: Option<HashMap<Address, Amount>>,
+    missed_votes: Option<HashMap<Address, u64>>,
+}
+
+impl ChainPoller {
+    async fn poll(&mut self, client: &NamadaClient, events: &ChainEvents) -> Result<(), ClientError> {
+        let height = client.query_last_height().await?.0;
+        if self.last_height.is_some_and(|last_height| height <= last_height) {
+            return Ok(());
+        }
+        let liveness_info = client.get_liveness_info().await?;
+        if let Some(last_height) = self.last_height {
+            let addresses: HashMap<_, _> = liveness_info.validators.iter()
+                .map(|validator| (validator.comet_address.as_str(), &validator.native_address))
+                .collect();
+            for height in (last_height + 1).max(height.saturating_sub(MAX_BLOCKS_PER_POLL) + 1)..=height {
+                let block = client.get_block(BlockHeight(height)).await?;
+                let proposer = addresses.get(block.block.header.proposer_address.to_string().as_str()).copied().cloned();
+                events.publish(ChainEvent::NewBlock(BlockResponse::new(&block.block_id, &block.block, proposer)));
+                self.last_height = Some(height);
+            }
+        }
+        self.last_height = Some(height);
+
+        let epoch = client.query_epoch().await?;
+        if self.epoch.is_some_and(|last_epoch| epoch != last_epoch) {
+            events.publish(ChainEvent::NewEpoch(NewEpochEvent { epoch: epoch.0, height }));
+        }
+        if self.epoch != Some(epoch) {
+            let consensus_set: HashMap<_, _> = client.get_consensus_validator_set(Some(epoch)).await?
+                .into_iter()
+                .map(|validator| (validator.address, validator.bonded_stake))
+                .collect();
+            if let Some(event) = self.consensus_set.as_ref().and_then(|previous| validator_set_change(epoch, previous, &consensus_set)) {
+                events.publish(ChainEvent::ValidatorSetChange(event));
+            }
+            self.consensus_set = Some(consensus_set);
+            self.epoch = Some(epoch);
+            publish_validator_snapshots(client, events, epoch, height).await;
+        }
+
+        let missed_votes: HashMap<_, _> = liveness_info.validators.iter()
+            .map(|validator| (validator.native_address.clone(), validator.missed_votes))
+            .collect();
+        if let Some(previous) = &self.missed_votes {
+            let mut validators: Vec<_> = liveness_info.validators.iter()
+                .filter(|validator| previous.get(&validator.native_address) != Some(&validator.missed_votes))
+                .map(|validator| ValidatorLivenessUpdate {
+                    address: validator.native_address.to_string(),
+                    consensus_address: validator.comet_address.clone(),
+                    missed_votes: validator.missed_votes,
+                })
+                .collect();
+            if !validators.is_empty() {
+                validators.sort_by(|a, b| a.address.cmp(&b.address));
+                events.publish(ChainEvent::LivenessUpdate(LivenessUpdateEvent {
+                    height,
+                    liveness_window_len: liveness_info.liveness_window_len,
+                    liveness_threshold: liveness_info.liveness_threshold.to_string(),
+                    validators,
+                }));
+            }
+        }
+        self.missed_votes = Some(missed_votes);
+        Ok(())
+    }
+}
+
+/// Query the watched validators at a new epoch and send their state to the
+/// streams watching them. Validators failing to be queried keep their last
+/// state until the next epoch.
+async fn publish_validator_snapshots(client: &NamadaClient, events: &ChainEvents, epoch: Epoch, height: u64) {
+    let snapshots: Vec<_> = futures::stream::iter(events.watched_validators())
+        .map(|address| async move {
+            let details = crate::validator_details(client, address.clone(), epoch).await;
+            (address, details)
+        })
+        .buffer_unordered(VALIDATOR_QUERIES_CONCURRENCY)
+        .collect()
+        .await;
+    for (address, details) in snapshots {
+        match details {
+            Ok(details) => events.publish_validator_snapshot(ValidatorSnapshot { height, details }),
+            Err(err) => warn!("Failed to query validator {} at epoch {}: {:?}", address, epoch, err),
+        }
+    }
+}
+
+/// Changes of the consensus validator set, if any
+pub fn validator_set_change(
+    epoch: Epoch,
+    previous: &HashMap<Address, Amount>,
+    current: &HashMap<Address, Amount>,
+) -> Option<ValidatorSetChangeEvent> {
+    let weighted = |address: &Address, stake: &Amount| WeightedValidatorResponse {
+        address: address.to_string(),
+        stake: stake.to_string(),
+    };
+    let mut added = Vec::new();
+    let mut updated = Vec::new();
+    for (address, stake) in current {
+        match previous.get(address) {
+            None => added.push(weighted(address, stake)),
+            Some(previous_stake) if previous_stake != stake => updated.push(weighted(address, stake)),
+            Some(_) => {}
+        }
+    }
+    let mut removed: Vec<_> = previous.keys()
+        .filter(|address| !current.contains_key(*address))
+        .map(|address| address.to_string())
+        .collect();
+    if added.is_empty() && removed.is_empty() && updated.is_empty() {
+        return None;
+    }
+
+    added.sort_by(|a, b| a.address.cmp(&b.address));
+    updated.sort_by(|a, b| a.address.cmp(&b.address));
+    removed.sort();
+    Some(ValidatorSetChangeEvent { epoch: epoch.0, added, removed, updated })
+}
+
+/// Events of a subscription, skipping the ones missed while lagging behind
+pub fn event_stream<T: Clone>(events: broadcast::Receiver<T>) -> impl Stream<Item = T> {
+    futures::stream::unfold(events, |mut events| async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => return Some((event, events)),
+                Err(broadcast::error::RecvError::Lagged(_)) => continue,
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+/// New epochs after `last_epoch`, starting with the `current` one if it is
+/// after `last_epoch`
+pub fn new_epochs(
+    current: NewEpochEvent,
+    last_epoch: Option<u64>,
+    events: broadcast::Receiver<Arc<ChainEvent>>,
+) -> impl Stream<Item = NewEpochEvent> {
+    let mut last_epoch = last_epoch;
+    futures::stream::iter([current])
+        .chain(event_stream(events).filter_map(|event| async move {
+            match &*event {
+                ChainEvent::NewEpoch(new_epoch) => Some(new_epoch.clone()),
+                _ => None,
+            }
+        }))
+        .filter(move |new_epoch| {
+            let is_new = last_epoch.is_none_or(|last_epoch| new_epoch.epoch > last_epoch);
+            if is_new {
+                last_epoch = Some(new_epoch.epoch);
+            }
+            futures::future::ready(is_new)
+        })
+}
+
+/// Apply a request of a WebSocket client to its topics, returning the reply
+pub fn handle_ws_request(request: &str, topics: &mut BTreeSet<Topic>) -> WsMessage<'static> {
+    match serde_json::from_str::<WsRequest>(request) {
+        Ok(WsRequest::Subscribe { topics: requested }) => topics.extend(requested),
+        Ok(WsRequest::Unsubscribe { topics: requested }) => requested.iter().for_each(|topic| {
+            topics.remove(topic);
+        }),
+        Err(err) => return WsMessage::Error { message: format!("Invalid request: {}", err) },
+    }
+    WsMessage::Subscribed { topics: topics.iter().copied().collect() }
+}
+
+/// Serve a WebSocket client: apply its requests, and send it the events of
+/// the topics it is subscribed to
+pub async fn ws_session(socket: WebSocket, mut events: broadcast::Receiver<Arc<ChainEvent>>, mut topics: BTreeSet<Topic>) {
+    let (mut sink, mut stream) = socket.split();
+    let to_text = |message: &WsMessage| Message::text(serde_json::to_string(message).unwrap_or_default());
+
+    if sink.send(to_text(&WsMessage::Subscribed { topics: topics.iter().copied().collect() })).await.is_err() {
+        return;
+    }
+    loop {
+        let reply = tokio::select! {
+            message = stream.next() => match message {
+                Some(Ok(message)) if message.is_close() => break,
+                Some(Ok(message)) => match message.to_str() {
+                    Ok(request) => to_text(&handle_ws_request(request, &mut topics)),
+                    // Pings are answered by the WebSocket layer
+                    Err(_) => continue,
+                },
+                _ => break,
+            },
+            event = events.recv() => match event {
+                Ok(event) if topics.contains(&event.topic()) => to_text(&WsMessage::Event(&event)),
+                Ok(_) => continue,
+                Err(broadcast::error::RecvError::Lagged(count)) => to_text(&WsMessage::Error {
+                    message: format!("Too slow, {} events were dropped", count),
+                }),
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+        };
+        if sink.send(reply).await.is_err() {
+            break;
+        }
+    }
+}
@@ -1,34 +1,62 @@
+#![recursion_limit = "256"]
+
 use std::sync::Arc;
 use warp::{Filter, Rejection, Reply};
-use tracing::{info, error};
+use tracing::{info, warn, error};
 use namada_core::address::Address;
-use namada_core::chain::BlockHeight;
+use namada_core::chain::{BlockHeight, Epoch};
+use namada_core::dec::Dec;
+use namada_core::token::Amount;
+use namada_proof_of_stake::types::{Slash, UnbondDetails};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::str::FromStr;
 use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::time::Duration;
+use futures::{StreamExt, TryStreamExt};
 use clap::Parser;
 use serde::Deserialize;
 
 mod models;
+mod cache;
 mod client;
 mod config;
+mod events;
+mod metrics;
+mod rate_limit;
+mod telemetry;
+mod uptime;
 #[cfg(test)]
 mod tests;
 
 use models::pos::*;
 use models::token::*;
+use models::gov::*;
+use models::block::*;
+use models::events::{NewEpochEvent, Topic};
 use models::error::{ApiError, handle_rejection};
+use rate_limit::RateLimiter;
 use config::{CliArgs, Config};
+use metrics::METRICS;
 
 /// Application state shared across all handlers
 #[derive(Clone)]
 pub struct AppState {
     namada_client: Arc<client::NamadaClient>,
+    /// Maximum number of validators queried concurrently by validators_details
+    /// and total_rewards
+    validators_details_concurrency: usize,
+    /// Deadline of a validators_details request
+    validators_details_timeout: Duration,
+    /// Chain events sent to the WebSocket clients
+    events: Arc<events::ChainEvents>,
 }
 
 #[derive(Debug, Deserialize)]
 pub struct ValidatorsQuery {
     pub page: Option<u32>,
     pub per_page: Option<u32>,
+    pub epoch: Option<u64>,
 }
 
 impl ValidatorsQuery {
@@ -52,22 +80,114 @@ impl ValidatorsQuery {
     }
 }
 
+#[derive(Debug, Deserialize)]
+pub struct EpochQuery {
+    pub epoch: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HeightQuery {
+    pub height: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct WsQuery {
+    /// Comma-separated topics subscribed to on connection
+    pub topics: Option<String>,
+}
+
+/// Resolve the `height` query parameter of a route served at a block height,
+/// defaulting to the last committed block.
+/// 
+/// Only blocks and storage reads, such as token balances, can be queried at a
+/// past height. The node serves the other queries, PoS and governance ones,
+/// at its last committed block only, so their routes take an epoch instead.
+pub async fn resolve_height(state: &AppState, height: Option<u64>) -> Result<BlockHeight, ApiError> {
+    let last = state.namada_client.query_last_height().await?;
+    
+    match height.map(BlockHeight) {
+        None => Ok(last),
+        Some(BlockHeight(0)) => Err(ApiError::InvalidHeight("Block height must be greater than 0".to_string())),
+        Some(height) if height > last => Err(ApiError::InvalidHeight(format!(
+            "Block height {} is beyond the last committed block height {}", height, last
+        ))),
+        Some(height) => Ok(height),
+    }
+}
+
+/// Resolve the `epoch` query parameter of a PoS route, defaulting to the
+/// current epoch. Past epochs are passed through to the node, future epochs
+/// are only accepted up to the pipeline offset, where PoS data already exists.
+/// 
+/// PoS data is indexed by epoch, so the epoch-indexed queries of a request
+/// all read the state of this one epoch.
+pub async fn resolve_epoch(state: &AppState, epoch: Option<u64>) -> Result<Epoch, ApiError> {
+    let current = state.namada_client.query_epoch().await?;
+    
+    let epoch = match epoch {
+        Some(epoch) => Epoch(epoch),
+        None => return Ok(current),
+    };
+    
+    if epoch > current {
+        let params = state.namada_client.get_pos_params().await?;
+        let last = Epoch(current.0.saturating_add(params.owned.pipeline_len));
+        if epoch > last {
+            return Err(ApiError::InvalidEpoch(format!(
+                "Epoch {} is beyond the pipeline epoch {} (current epoch {})", epoch, last, current
+            )));
+        }
+    }
+    
+    Ok(epoch)
+}
+
+/// Check the `epoch` query parameter of a route the node only serves at its
+/// last block, so for the current epoch only
+pub async fn require_current_epoch(state: &AppState, epoch: Option<u64>) -> Result<(), ApiError> {
+    let Some(epoch) = epoch else {
+        return Ok(());
+    };
+    let current = state.namada_client.query_epoch().await?;
+    if epoch != current.0 {
+        return Err(ApiError::InvalidEpoch(format!(
+            "Epoch {} can't be queried, only the current epoch {} is served", epoch, current
+        )));
+    }
+    Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize tracing
-    tracing_subscriber::fmt::init();
-    
-    // Parse command line arguments
+    // Parse command line arguments, and load the configuration with a
+    // default logger until the configured one is installed
     let args = CliArgs::parse();
-    let config = Config::load(args)?;
+    let config = tracing::subscriber::with_default(telemetry::bootstrap_subscriber(), || Config::load(args))?;
+    let _telemetry = telemetry::init(&config)?;
+    
+    info!("Starting Namada API with {} RPC endpoint(s)", config.rpc_endpoints.len());
     
-    info!("Starting Namada API with RPC URL: {}", config.rpc_url);
+    // Create Namada client with configured endpoints, and probe their health
+    let namada_client = Arc::new(client::NamadaClient::with_endpoints(config.rpc_endpoints.clone(), config.rpc_max_lag_blocks).await?);
+    namada_client.spawn_health_probe(Duration::from_millis(config.rpc_health_interval_ms));
     
-    // Create Namada client with configured URL
-    let namada_client = Arc::new(client::NamadaClient::new(config.rpc_url).await?);
+    // Monitor the uptime of validators
+    if config.uptime_monitor.enabled {
+        info!("Monitoring the uptime of validators over windows of {:?} blocks", config.uptime_monitor.windows);
+        uptime::UptimeMonitor::new(namada_client.clone(), config.uptime_monitor.clone())?.spawn();
+    }
+    
+    // Poll the chain events of the WebSocket clients
+    let events = Arc::new(events::ChainEvents::new());
+    events.spawn_poller(namada_client.clone(), Duration::from_millis(config.events_poll_interval_ms));
     
     // Create application state
-    let state = Arc::new(AppState { namada_client });
+    let state = Arc::new(AppState {
+        namada_client,
+        validators_details_concurrency: config.validators_details_concurrency,
+        validators_details_timeout: Duration::from_millis(config.validators_details_timeout_ms),
+        events,
+    });
     
     // Documentation route
     let docs = warp::path("api")
@@ -75,6 +195,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .and(warp::get())
         .and_then(serve_docs);
     
+    // Metrics route
+    let metrics = warp::path("metrics")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(get_metrics);
+    
     // Health routes
     let health = warp::path("api")
         .and(warp::path("health"))
@@ -88,14 +215,24 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .and(warp::get())
         .and(with_state(state.clone()))
         .and_then(rpc_health_check);
+        
+    let cache_status = warp::path("api")
+        .and(warp::path("health"))
+        .and(warp::path("cache_status"))
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(cache_status);
     
     // PoS routes
     let liveness_info = warp::path("api")
         .and(warp::path("pos"))
         .and(warp::path("liveness_info"))
         .and(warp::get())
+        .and(warp::query::<EpochQuery>())
         .and(with_state(state.clone()))
-        .and_then(get_liveness_info);
+        .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+            get_liveness_info(state, query).await
+        });
         
     let validator_by_tm = warp::path("api")
         .and(warp::path("pos"))
@@ -112,17 +249,22 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .and(warp::path("validator_details"))
         .and(warp::path::param::<String>())
         .and(warp::get())
+        .and(warp::query::<EpochQuery>())
         .and(with_state(state.clone()))
-        .and_then(|address: String, state: Arc<AppState>| async move {
-            get_validator_details(state, address).await
+        .and_then(|address: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_validator_details(state, address, query).await
         });
         
     let all_validators = warp::path("api")
         .and(warp::path("pos"))
         .and(warp::path("validators"))
+        .and(warp::path::end())
         .and(warp::get())
+        .and(warp::query::<EpochQuery>())
         .and(with_state(state.clone()))
-        .and_then(get_all_validators);
+        .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+            get_all_validators(state, query).await
+        });
         
     let validators_details = warp::path("api")
         .and(warp::path("pos"))
@@ -139,16 +281,160 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .and(warp::path("validator_set"))
         .and(warp::path("consensus"))
         .and(warp::get())
+        .and(warp::query::<EpochQuery>())
         .and(with_state(state.clone()))
-        .and_then(get_consensus_validator_set);
+        .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+            get_consensus_validator_set(state, query).await
+        });
 
     let below_capacity_validator_set = warp::path("api")
         .and(warp::path("pos"))
         .and(warp::path("validator_set"))
         .and(warp::path("below_capacity"))
         .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+            get_below_capacity_validator_set(state, query).await
+        });
+
+    let delegations = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("delegations"))
+        .and(warp::path::param::<String>())
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|address: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_delegations(state, address, query).await
+        });
+
+    let bonds = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("bonds"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("to"))
+        .and(warp::path::param::<String>())
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|source: String, validator: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_bonds(state, source, validator, query).await
+        });
+
+    let unbonds = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("unbonds"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("to"))
+        .and(warp::path::param::<String>())
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
         .and(with_state(state.clone()))
-        .and_then(get_below_capacity_validator_set);
+        .and_then(|source: String, validator: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_unbonds(state, source, validator, query).await
+        });
+
+    let withdrawable = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("withdrawable"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::param::<String>())
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|source: String, validator: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_withdrawable(state, source, validator, query).await
+        });
+
+    let source_withdrawable = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("withdrawable"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|source: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_source_withdrawable(state, source, query).await
+        });
+
+    let rewards = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("rewards"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::param::<String>())
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|validator: String, delegator: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_rewards(state, validator, Some(delegator), query).await
+        });
+
+    let self_bond_rewards = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("rewards"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|validator: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_rewards(state, validator, None, query).await
+        });
+
+    let total_rewards = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("total_rewards"))
+        .and(warp::path::param::<String>())
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|delegator: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_total_rewards(state, delegator, query).await
+        });
+
+    let validator_slashes = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("validators"))
+        .and(warp::path::param::<String>())
+        .and(warp::path("slashes"))
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|address: String, query: EpochQuery, state: Arc<AppState>| async move {
+            get_validator_slashes(state, address, query).await
+        });
+
+    let all_slashes = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("slashes"))
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+            get_all_slashes(state, query).await
+        });
+
+    let total_stake = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("total_stake"))
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+            get_total_stake(state, query).await
+        });
+
+    let pos_parameters = warp::path("api")
+        .and(warp::path("pos"))
+        .and(warp::path("parameters"))
+        .and(warp::get())
+        .and(warp::query::<EpochQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+            get_pos_parameters(state, query).await
+        });
 
     // Token routes
     let token_balance = warp::path("api")
@@ -166,9 +452,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .and(warp::path("total_supply"))
         .and(warp::path::param::<String>())
         .and(warp::get())
+        .and(warp::query::<HeightQuery>())
         .and(with_state(state.clone()))
-        .and_then(|token: String, state: Arc<AppState>| async move {
-            get_token_total_supply(state, token).await
+        .and_then(|token: String, query: HeightQuery, state: Arc<AppState>| async move {
+            get_token_total_supply(state, token, query).await
         });
 
     let native_token = warp::path("api")
@@ -178,10 +465,143 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .and(with_state(state.clone()))
         .and_then(get_native_token);
     
-    // Combine all routes
+    // Governance routes
+    let gov_proposals = warp::path("api")
+        .and(warp::path("gov"))
+        .and(warp::path("proposals"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query::<ProposalsQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|query: ProposalsQuery, state: Arc<AppState>| async move {
+            get_proposals(state, query).await
+        });
+
+    let gov_proposal = warp::path("api")
+        .and(warp::path("gov"))
+        .and(warp::path("proposals"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(|id: u64, state: Arc<AppState>| async move {
+            get_proposal(state, id).await
+        });
+
+    let gov_proposal_votes = warp::path("api")
+        .and(warp::path("gov"))
+        .and(warp::path("proposals"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path("votes"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(|id: u64, state: Arc<AppState>| async move {
+            get_proposal_votes(state, id).await
+        });
+
+    let gov_proposal_vote = warp::path("api")
+        .and(warp::path("gov"))
+        .and(warp::path("proposals"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path("votes"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(|id: u64, address: String, state: Arc<AppState>| async move {
+            get_effective_vote(state, id, address).await
+        });
+
+    let gov_parameters = warp::path("api")
+        .and(warp::path("gov"))
+        .and(warp::path("parameters"))
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(get_gov_parameters);
+    
+    // Chain routes
+    let latest_block = warp::path("api")
+        .and(warp::path("blocks"))
+        .and(warp::path("latest"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(get_latest_block);
+
+    let block = warp::path("api")
+        .and(warp::path("blocks"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(|height: u64, state: Arc<AppState>| async move {
+            get_block(state, height).await
+        });
+
+    let block_signatures = warp::path("api")
+        .and(warp::path("blocks"))
+        .and(warp::path::param::<u64>())
+        .and(warp::path("signatures"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(|height: u64, state: Arc<AppState>| async move {
+            get_block_signatures(state, height).await
+        });
+
+    let tx = warp::path("api")
+        .and(warp::path("tx"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(with_state(state.clone()))
+        .and_then(|hash: String, state: Arc<AppState>| async move {
+            get_tx(state, hash).await
+        });
+    
+    // Server-sent events routes
+    let epoch_stream = warp::path("api")
+        .and(warp::path("stream"))
+        .and(warp::path("epoch"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::header::optional::<u64>("last-event-id"))
+        .and(with_state(state.clone()))
+        .and_then(|last_event_id: Option<u64>, state: Arc<AppState>| async move {
+            stream_epoch(state, last_event_id).await
+        });
+    
+    let validator_stream = warp::path("api")
+        .and(warp::path("stream"))
+        .and(warp::path("validators"))
+        .and(warp::path::param::<String>())
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::header::optional::<u64>("last-event-id"))
+        .and(with_state(state.clone()))
+        .and_then(|address: String, last_event_id: Option<u64>, state: Arc<AppState>| async move {
+            stream_validator(state, address, last_event_id).await
+        });
+    
+    // WebSocket route
+    let ws = warp::path("api")
+        .and(warp::path("ws"))
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::ws())
+        .and(warp::query::<WsQuery>())
+        .and(with_state(state.clone()))
+        .and_then(|ws: warp::ws::Ws, query: WsQuery, state: Arc<AppState>| async move {
+            ws_connect(state, ws, query).await
+        });
+    
+    // Combine all routes, behind the rate limiter
     let routes = docs
+        .or(metrics)
         .or(health)
         .or(rpc_health)
+        .or(cache_status)
         .or(liveness_info)
         .or(validator_by_tm)
         .or(validator_details)
@@ -189,21 +609,108 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         .or(validators_details)
         .or(consensus_validator_set)
         .or(below_capacity_validator_set)
+        .or(delegations)
+        .or(bonds)
+        .or(unbonds)
+        .or(withdrawable)
+        .or(source_withdrawable)
+        .or(rewards)
+        .or(self_bond_rewards)
+        .or(total_rewards)
+        .or(validator_slashes)
+        .or(all_slashes)
+        .or(total_stake)
+        .or(pos_parameters)
         .or(token_balance)
         .or(token_total_supply)
         .or(native_token)
-        .with(warp::cors().allow_any_origin())
+        .or(gov_proposals)
+        .or(gov_proposal)
+        .or(gov_proposal_votes)
+        .or(gov_proposal_vote)
+        .or(gov_parameters)
+        .or(latest_block)
+        .or(block)
+        .or(block_signatures)
+        .or(tx)
+        .or(epoch_stream)
+        .or(validator_stream)
+        .or(ws);
+    let rate_limiter = Arc::new(RateLimiter::new(&config));
+    let routes = rate_limit::rate_limit(rate_limiter)
+        .and(routes)
+        .with(config.cors())
         .recover(handle_rejection);
     
-    // Start the server
-    let addr = ([127, 0, 0, 1], config.port);
-    info!("Starting server on {:?}", addr);
+    // Serve each request in a span carrying its ID, route, status and duration
+    let routes = telemetry::request_context()
+        .and(routes)
+        .map(telemetry::finish_request)
+        .with(warp::trace(telemetry::request_span))
+        .with(warp::log::custom(|info| {
+            METRICS.record_request(info.path(), info.method().as_str(), info.status().as_u16(), info.elapsed());
+        }));
+    
+    // Start the server, with TLS if a certificate is configured
+    let addr = SocketAddr::new(config.bind_address, config.port);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let shutdown = async {
+        shutdown_rx.await.ok();
+    };
+    let server = match (&config.tls_cert_path, &config.tls_key_path) {
+        (Some(cert_path), Some(key_path)) => {
+            let (addr, server) = warp::serve(routes)
+                .tls()
+                .cert_path(cert_path)
+                .key_path(key_path)
+                .try_bind_with_graceful_shutdown(addr, shutdown)?;
+            info!("Starting server on https://{}", addr);
+            tokio::spawn(server)
+        }
+        _ => {
+            let (addr, server) = warp::serve(routes)
+                .try_bind_with_graceful_shutdown(addr, shutdown)?;
+            info!("Starting server on http://{}", addr);
+            tokio::spawn(server)
+        }
+    };
     
-    warp::serve(routes).run(addr).await;
+    // On SIGTERM or SIGINT, stop accepting connections and give in-flight
+    // requests some time to complete
+    shutdown_signal().await;
+    let shutdown_timeout = Duration::from_millis(config.shutdown_timeout_ms);
+    info!("Shutting down, waiting up to {} ms for in-flight requests", shutdown_timeout.as_millis());
+    shutdown_tx.send(()).ok();
+    match tokio::time::timeout(shutdown_timeout, server).await {
+        Ok(_) => info!("Server stopped"),
+        Err(_) => warn!("In-flight requests did not complete within {} ms, exiting", shutdown_timeout.as_millis()),
+    }
     
     Ok(())
 }
 
+/// Wait for SIGINT (Ctrl+C) or, on Unix, SIGTERM
+async fn shutdown_signal() {
+    let interrupt = async {
+        tokio::signal::ctrl_c().await.expect("Failed to listen for SIGINT");
+    };
+    
+    #[cfg(unix)]
+    let terminate = async {
+        tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("Failed to listen for SIGTERM")
+            .recv()
+            .await;
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+    
+    tokio::select! {
+        _ = interrupt => {},
+        _ = terminate => {},
+    }
+}
+
 /// Helper function to inject application state into handlers
 pub fn with_state(state: Arc<AppState>) -> impl Filter<Extract = (Arc<AppState>,), Error = Infallible> + Clone {
     warp::any().map(move || state.clone())
@@ -233,12 +740,27 @@ pub async fn health_check() -> Result<impl Reply, Rejection> {
 /// # Endpoint
 /// `GET /api/health/rpc_status`
 /// 
+/// Probes every configured RPC endpoint, and succeeds if at least one of
+/// them is healthy.
+/// 
 /// # Response
 /// Success:
 /// ```json
 /// {
 ///     "status": "ok",
-///     "rpc_url": "https://rpc-1.namada.nodes.guru"
+///     "rpc_url": "https://rpc-1.namada.nodes.guru",
+///     "endpoints": [
+///         {
+///             "url": "https://rpc-1.namada.nodes.guru",
+///             "priority": 0,
+///             "weight": 1,
+///             "healthy": true,
+///             "height": 12345,
+///             "lag": 0,
+///             "lagging": false,
+///             "error": null
+///         }
+///     ]
 /// }
 /// ```
 /// 
@@ -256,7 +778,8 @@ pub async fn rpc_health_check(state: Arc<AppState>) -> Result<impl Reply, Reject
         Ok(_) => {
             serde_json::json!({
                 "status": "ok",
-                "rpc_url": state.namada_client.rpc_url()
+                "rpc_url": state.namada_client.rpc_url(),
+                "endpoints": state.namada_client.endpoint_statuses()
             })
         },
         Err(err) => {
@@ -272,10 +795,56 @@ pub async fn rpc_health_check(state: Arc<AppState>) -> Result<impl Reply, Reject
     Ok(warp::reply::json(&response))
 }
 
+/// Prometheus metrics endpoint
+/// 
+/// # Endpoint
+/// `GET /metrics`
+/// 
+/// # Response
+/// Metrics in the Prometheus text format, prefixed with `namada_api_`:
+/// - `http_requests_total` and `http_request_duration_seconds`, by route, method and status
+/// - `rpc_calls_total`, `rpc_errors_total` and `rpc_call_duration_seconds`, by client method
+/// - `cache_hits_total`, `cache_misses_total`, `cache_entries` and `cache_hit_ratio`, by query kind
+/// - `chain_epoch` and `chain_height`
+pub async fn get_metrics(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
+    Ok(warp::reply::with_header(
+        METRICS.render(&state.namada_client),
+        "content-type",
+        "text/plain; version=0.0.4",
+    ))
+}
+
+/// Query cache statistics endpoint
+/// 
+/// # Endpoint
+/// `GET /api/health/cache_status`
+/// 
+/// # Response
+/// ```json
+/// {
+///     "epoch": 42,
+///     "kinds": {
+///         "validator_sets": {
+///             "hits": 120,
+///             "misses": 4,
+///             "entries": 2,
+///             "hit_ratio": 0.967741935483871
+///         }
+///     }
+/// }
+/// ```
+pub async fn cache_status(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
+    Ok(warp::reply::json(&state.namada_client.cache_stats()))
+}
+
 /// Get liveness information for validators
 /// 
 /// # Endpoint
-/// `GET /api/pos/liveness_info`
+/// `GET /api/pos/liveness_info?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `epoch`: Optional epoch, which must be the current one, as the node only
+///   serves the liveness of its last block
 /// 
 /// # Response
 /// ```json
@@ -291,14 +860,14 @@ pub async fn rpc_health_check(state: Arc<AppState>) -> Result<impl Reply, Reject
 ///     ]
 /// }
 /// ```
-pub async fn get_liveness_info(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
+pub async fn get_liveness_info(state: Arc<AppState>, query: EpochQuery) -> Result<impl Reply, Rejection> {
+    require_current_epoch(&state, query.epoch).await?;
+    
     // Query liveness info
     let liveness_info = state.namada_client.get_liveness_info().await
         .map_err(|err| {
             error!("Failed to get liveness info: {}", err);
-            warp::reject::custom(
-                ApiError::QueryError(format!("Failed to fetch liveness info: {}", err))
-            )
+            warp::reject::custom(ApiError::from(err))
         })?;
     
     // Build validators list from the response
@@ -349,9 +918,7 @@ pub async fn get_validator_by_tm_addr(
     let validator_address = state.namada_client.validator_by_tm_addr(tm_addr.clone()).await
         .map_err(|err| {
             error!("Failed to query validator by Tendermint address: {}", err);
-            warp::reject::custom(
-                ApiError::QueryError(format!("Failed to query validator by Tendermint address: {}", err))
-            )
+            warp::reject::custom(ApiError::from(err))
         })?;
     
     match validator_address {
@@ -373,15 +940,17 @@ pub async fn get_validator_by_tm_addr(
 /// Get detailed validator information
 /// 
 /// # Endpoint
-/// `GET /api/pos/validator_details/{address}`
+/// `GET /api/pos/validator_details/{address}?epoch={epoch}`
 /// 
 /// # Parameters
 /// - `address`: Namada address of the validator
+/// - `epoch`: Optional epoch (default: current epoch)
 /// 
 /// # Response
 /// ```json
 /// {
 ///     "address": "tnam1q...",
+///     "epoch": 120,
 ///     "state": "active",
 ///     "stake": "1000000",
 ///     "commission_rate": "0.05",
@@ -397,33 +966,42 @@ pub async fn get_validator_by_tm_addr(
 async fn get_validator_details(
     state: Arc<AppState>,
     address: String,
+    query: EpochQuery,
 ) -> Result<impl Reply, Rejection> {
     // Validate address format
     let address = Address::from_str(&address)
         .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid address format: {}", e))))?;
     
     // Check if address is a validator
-    let is_validator = state.namada_client.is_validator(&address).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+    let is_validator = state.namada_client.is_validator(&address).await?;
     
     if !is_validator {
         return Err(warp::reject::custom(ApiError::NotFound(format!("Address {} is not a validator", address))));
     }
     
-    let epoch = state.namada_client.query_epoch().await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+    let epoch = resolve_epoch(&state, query.epoch).await?;
     
-    let (state_info, epoch) = state.namada_client.get_validator_state(&address, Some(epoch)).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
-    
-    let stake = state.namada_client.get_validator_stake(epoch, &address).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
-    
-    let (metadata, commission) = state.namada_client.query_metadata(&address, Some(epoch)).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+    Ok(warp::reply::json(&validator_details(&state.namada_client, address, epoch).await?))
+}
+
+/// Query the state, stake and metadata of a validator at `epoch`. The three
+/// queries are independent, so they are issued concurrently.
+async fn validator_details(
+    client: &client::NamadaClient,
+    address: Address,
+    epoch: Epoch,
+) -> Result<ValidatorDetailsResponse, ApiError> {
+    let (validator_state, stake, metadata) = tokio::try_join!(
+        client.get_validator_state(&address, Some(epoch)),
+        client.get_validator_stake(epoch, &address),
+        client.query_metadata(&address, Some(epoch)),
+    )?;
+    let (state_info, epoch) = validator_state;
+    let (metadata, commission) = metadata;
     
-    Ok(warp::reply::json(&ValidatorDetailsResponse {
+    Ok(ValidatorDetailsResponse {
         address: address.to_string(),
+        epoch: epoch.0,
         state: state_info.map_or("unknown".to_string(), |s| format!("{:?}", s)),
         stake: stake.to_string(),
         commission_rate: commission.commission_rate.map_or("0".to_string(), |r| r.to_string()),
@@ -436,50 +1014,63 @@ async fn get_validator_details(
             name: m.name,
             avatar: m.avatar,
         }),
-    }))
+    })
 }
 
 /// Get list of all validators (simple list)
 /// 
 /// # Endpoint
-/// `GET /api/pos/validators`
+/// `GET /api/pos/validators?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `epoch`: Optional epoch (default: current epoch)
 /// 
 /// # Response
 /// ```json
 /// {
+///     "epoch": 120,
 ///     "validators": [
 ///         "tnam1q...",
 ///         "tnam1q..."
 ///     ]
 /// }
 /// ```
-async fn get_all_validators(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
-    let epoch = state.namada_client.query_epoch().await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+async fn get_all_validators(
+    state: Arc<AppState>,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    let epoch = resolve_epoch(&state, query.epoch).await?;
     
-    let validators = state.namada_client.get_all_validators(Some(epoch)).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+    let validators = state.namada_client.get_all_validators(Some(epoch)).await?;
     
     Ok(warp::reply::json(&serde_json::json!({
+        "epoch": epoch.0,
         "validators": validators.into_iter().map(|addr| addr.to_string()).collect::<Vec<String>>()
     })))
 }
 
 /// Get detailed information about all validators with pagination
 /// 
+/// The validators of a page are queried concurrently, up to the configured
+/// concurrency limit. The request fails with `504` if it is not served
+/// within the configured deadline.
+/// 
 /// # Endpoint
-/// `GET /api/pos/validators_details?page={page}&per_page={per_page}`
+/// `GET /api/pos/validators_details?page={page}&per_page={per_page}&epoch={epoch}`
 /// 
 /// # Parameters
 /// - `page`: Page number (default: 1)
 /// - `per_page`: Number of validators per page (default: 10, max: 50)
+/// - `epoch`: Optional epoch (default: current epoch)
 /// 
 /// # Response
 /// ```json
 /// {
+///     "epoch": 120,
 ///     "validators": [
 ///         {
 ///             "address": "tnam1q...",
+///             "epoch": 120,
 ///             "state": "active",
 ///             "stake": "1000000",
 ///             "commission_rate": "0.05",
@@ -511,74 +1102,66 @@ async fn get_validators_details(
     let page = query.page.unwrap_or(1);
     let per_page = query.per_page.unwrap_or(10);
     
-    let epoch = state.namada_client.query_epoch().await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
-    
-    let validators = state.namada_client.get_all_validators(Some(epoch)).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
-    
-    let total = validators.len();
-    let total_pages = (total as f64 / per_page as f64).ceil() as u32;
-    
-    // Validate page number against total pages
-    if page > total_pages && total_pages > 0 {
-        return Err(warp::reject::custom(ApiError::InvalidPagination(
-            format!("Page number {} exceeds total pages {}", page, total_pages)
-        )));
-    }
-    
-    // Calculate start and end indices for the current page
-    let start = ((page - 1) * per_page) as usize;
-    let end = (start + per_page as usize).min(total);
-    
-    // Get validators for the current page
-    let mut responses = Vec::new();
-    for address in validators.into_iter().skip(start).take(end - start) {
-        let (state_info, epoch) = state.namada_client.get_validator_state(&address, Some(epoch)).await
-            .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+    let details = async {
+        let epoch = resolve_epoch(&state, query.epoch).await?;
         
-        let stake = state.namada_client.get_validator_stake(epoch, &address).await
-            .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+        let validators = state.namada_client.get_all_validators(Some(epoch)).await?;
         
-        let (metadata, commission) = state.namada_client.query_metadata(&address, Some(epoch)).await
-            .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+        let total = validators.len();
+        let total_pages = (total as f64 / per_page as f64).ceil() as u32;
         
-        responses.push(ValidatorDetailsResponse {
-            address: address.to_string(),
-            state: state_info.map_or("unknown".to_string(), |s| format!("{:?}", s)),
-            stake: stake.to_string(),
-            commission_rate: commission.commission_rate.map_or("0".to_string(), |r| r.to_string()),
-            max_commission_change_per_epoch: commission.max_commission_change_per_epoch.map_or("0".to_string(), |r| r.to_string()),
-            metadata: metadata.map(|m| ValidatorMetadata {
-                email: m.email,
-                description: m.description,
-                website: m.website,
-                discord_handle: m.discord_handle,
-                name: m.name,
-                avatar: m.avatar,
-            }),
-        });
-    }
-    
-    Ok(warp::reply::json(&serde_json::json!({
-        "validators": responses,
-        "pagination": {
-            "total": total,
-            "page": page,
-            "per_page": per_page,
-            "total_pages": total_pages
+        // Validate page number against total pages
+        if page > total_pages && total_pages > 0 {
+            return Err(ApiError::InvalidPagination(
+                format!("Page number {} exceeds total pages {}", page, total_pages)
+            ));
         }
-    })))
+        
+        // Calculate start and end indices for the current page
+        let start = ((page - 1) * per_page) as usize;
+        let end = (start + per_page as usize).min(total);
+        
+        // Query the validators of the current page concurrently, keeping their order,
+        // with at most `validators_details_concurrency` validators in flight
+        let responses = futures::stream::iter(validators.into_iter().skip(start).take(end - start))
+            .map(|address| validator_details(&state.namada_client, address, epoch))
+            .buffered(state.validators_details_concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+        
+        Ok(serde_json::json!({
+            "epoch": epoch.0,
+            "validators": responses,
+            "pagination": {
+                "total": total,
+                "page": page,
+                "per_page": per_page,
+                "total_pages": total_pages
+            }
+        }))
+    };
+    
+    // The deadline covers the whole request, the epoch and validator list included
+    let response = tokio::time::timeout(state.validators_details_timeout, details).await
+        .map_err(|_| ApiError::Timeout(format!(
+            "Validator details were not received within {} ms", state.validators_details_timeout.as_millis()
+        )))??;
+    
+    Ok(warp::reply::json(&response))
 }
 
 /// Get consensus validator set
 /// 
 /// # Endpoint
-/// `GET /api/pos/validator_set/consensus`
+/// `GET /api/pos/validator_set/consensus?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `epoch`: Optional epoch (default: current epoch)
 /// 
 /// # Response
 /// ```json
 /// {
+///     "epoch": 120,
 ///     "validators": [
 ///         {
 ///             "address": "tnam1q...",
@@ -587,11 +1170,16 @@ async fn get_validators_details(
 ///     ]
 /// }
 /// ```
-async fn get_consensus_validator_set(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
-    let validators = state.namada_client.get_consensus_validator_set(None).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+async fn get_consensus_validator_set(
+    state: Arc<AppState>,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let validators = state.namada_client.get_consensus_validator_set(Some(epoch)).await?;
     
     let response = ValidatorSetResponse {
+        epoch: epoch.0,
         validators: validators.into_iter().map(|v| WeightedValidatorResponse {
             address: v.address.to_string(),
             stake: v.bonded_stake.to_string(),
@@ -604,11 +1192,15 @@ async fn get_consensus_validator_set(state: Arc<AppState>) -> Result<impl Reply,
 /// Get below-capacity validator set
 /// 
 /// # Endpoint
-/// `GET /api/pos/validator_set/below_capacity`
+/// `GET /api/pos/validator_set/below_capacity?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `epoch`: Optional epoch (default: current epoch)
 /// 
 /// # Response
 /// ```json
 /// {
+///     "epoch": 120,
 ///     "validators": [
 ///         {
 ///             "address": "tnam1q...",
@@ -617,11 +1209,16 @@ async fn get_consensus_validator_set(state: Arc<AppState>) -> Result<impl Reply,
 ///     ]
 /// }
 /// ```
-async fn get_below_capacity_validator_set(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
-    let validators = state.namada_client.get_below_capacity_validator_set(None).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+async fn get_below_capacity_validator_set(
+    state: Arc<AppState>,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let validators = state.namada_client.get_below_capacity_validator_set(Some(epoch)).await?;
     
     let response = ValidatorSetResponse {
+        epoch: epoch.0,
         validators: validators.into_iter().map(|v| WeightedValidatorResponse {
             address: v.address.to_string(),
             stake: v.bonded_stake.to_string(),
@@ -631,15 +1228,694 @@ async fn get_below_capacity_validator_set(state: Arc<AppState>) -> Result<impl R
     Ok(warp::reply::json(&response))
 }
 
-/// Get token balance
+/// Get the delegations of an address
 /// 
 /// # Endpoint
-/// `GET /api/token/balance?token={token}&owner={owner}&height={height}`
+/// `GET /api/pos/delegations/{address}?epoch={epoch}`
 /// 
 /// # Parameters
-/// - `token`: Token address
+/// - `address`: Namada address of the delegator
+/// - `epoch`: Optional epoch (default: current epoch)
+/// 
+/// # Response
+/// ```json
+/// {
+///     "epoch": 120,
+///     "delegations": [
+///         {
+///             "validator": "tnam1q...",
+///             "amount": "1000000"
+///         }
+///     ]
+/// }
+/// ```
+async fn get_delegations(
+    state: Arc<AppState>,
+    address: String,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    // Validate address format
+    let address = Address::from_str(&address)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid address format: {}", e))))?;
+    
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let mut delegations = state.namada_client.get_delegations(&address, Some(epoch)).await?;
+    
+    // Keep the output stable regardless of the order the node returns bonds in
+    delegations.sort_by_key(|(validator, _)| validator.to_string());
+    
+    Ok(warp::reply::json(&DelegationsResponse {
+        epoch: epoch.0,
+        delegations: delegations.into_iter().map(|(validator, amount)| Delegation {
+            validator: validator.to_string(),
+            amount: amount.to_string(),
+        }).collect(),
+    }))
+}
+
+/// Get bonds from a source to a validator
+/// 
+/// # Endpoint
+/// `GET /api/pos/bonds/{source}/to/{validator}?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `source`: Namada address of the bond owner (the validator itself for self-bonds)
+/// - `validator`: Namada address of the validator
+/// - `epoch`: Optional epoch, up to the pipeline epoch (default: current epoch).
+///   Only the bonds started by this epoch are returned. Tokens unbonded since
+///   are no longer bonds, so past epochs only show the bonds still held.
+/// 
+/// # Response
+/// ```json
+/// {
+///     "source": "tnam1q...",
+///     "validator": "tnam1q...",
+///     "epoch": 120,
+///     "bonds": [
+///         {
+///             "start_epoch": 100,
+///             "amount": "1000000",
+///             "slashed_amount": null
+///         }
+///     ]
+/// }
+/// ```
+async fn get_bonds(
+    state: Arc<AppState>,
+    source: String,
+    validator: String,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    // Validate address formats
+    let source_addr = Address::from_str(&source)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid source address: {}", e))))?;
+    let validator_addr = Address::from_str(&validator)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid validator address: {}", e))))?;
+    
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let details = state.namada_client.get_bonds_and_unbonds(Some(&source_addr), Some(&validator_addr)).await?;
+    
+    let mut bonds: Vec<_> = details.into_values()
+        .flat_map(|detail| detail.bonds)
+        .filter(|bond| bond.start <= epoch)
+        .collect();
+    bonds.sort_by_key(|bond| bond.start);
+    
+    Ok(warp::reply::json(&BondsResponse {
+        source,
+        validator,
+        epoch: epoch.0,
+        bonds: bonds.into_iter().map(|bond| Bond {
+            start_epoch: bond.start.0,
+            amount: bond.amount.to_string(),
+            slashed_amount: bond.slashed_amount.map(|a| a.to_string()),
+        }).collect(),
+    }))
+}
+
+/// Get unbonds from a source to a validator
+/// 
+/// # Endpoint
+/// `GET /api/pos/unbonds/{source}/to/{validator}?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `source`: Namada address of the bond owner (the validator itself for self-bonds)
+/// - `validator`: Namada address of the validator
+/// - `epoch`: Optional epoch, up to the pipeline epoch (default: current epoch).
+///   Only the unbonds submitted by this epoch are returned. Withdrawn unbonds
+///   are no longer stored, so past epochs only show the ones not withdrawn yet.
+/// 
+/// # Response
+/// ```json
+/// {
+///     "source": "tnam1q...",
+///     "validator": "tnam1q...",
+///     "epoch": 120,
+///     "unbonds": [
+///         {
+///             "start_epoch": 100,
+///             "withdraw_epoch": 130,
+///             "amount": "500000",
+///             "slashed_amount": null
+///         }
+///     ]
+/// }
+/// ```
+async fn get_unbonds(
+    state: Arc<AppState>,
+    source: String,
+    validator: String,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    // Validate address formats
+    let source_addr = Address::from_str(&source)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid source address: {}", e))))?;
+    let validator_addr = Address::from_str(&validator)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid validator address: {}", e))))?;
+    
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    let params = state.namada_client.get_pos_params().await?;
+    
+    let details = state.namada_client.get_bonds_and_unbonds(Some(&source_addr), Some(&validator_addr)).await?;
+    
+    // An unbond becomes withdrawable a fixed number of epochs after it is submitted
+    let offset = params.owned.withdrawable_epoch_offset();
+    let mut unbonds: Vec<_> = details.into_values()
+        .flat_map(|detail| detail.unbonds)
+        .filter(|unbond| unbond.withdraw.0.saturating_sub(offset) <= epoch.0)
+        .collect();
+    unbonds.sort_by_key(|unbond| (unbond.withdraw, unbond.start));
+    
+    Ok(warp::reply::json(&UnbondsResponse {
+        source,
+        validator,
+        epoch: epoch.0,
+        unbonds: unbonds.into_iter().map(|unbond| Unbond {
+            start_epoch: unbond.start.0,
+            withdraw_epoch: unbond.withdraw.0,
+            amount: unbond.amount.to_string(),
+            slashed_amount: unbond.slashed_amount.map(|a| a.to_string()),
+        }).collect(),
+    }))
+}
+
+/// Split unbonds into the amount withdrawable at `epoch` and the amounts that
+/// only become withdrawable later, grouped by withdraw epoch. Slashed tokens
+/// can never be withdrawn, so they are left out of both.
+pub fn withdrawal_schedule<'a>(
+    unbonds: impl IntoIterator<Item = &'a UnbondDetails>,
+    epoch: Epoch,
+) -> Result<(Amount, BTreeMap<Epoch, Amount>), ApiError> {
+    let overflow = || ApiError::InternalError("Withdrawable amount overflow".to_string());
+    
+    let mut withdrawable = Amount::zero();
+    let mut pending = BTreeMap::<Epoch, Amount>::new();
+    for unbond in unbonds {
+        let amount = unbond.amount
+            .checked_sub(unbond.slashed_amount.unwrap_or_default())
+            .unwrap_or_default();
+        if unbond.withdraw <= epoch {
+            withdrawable = withdrawable.checked_add(amount).ok_or_else(overflow)?;
+        } else {
+            let entry = pending.entry(unbond.withdraw).or_default();
+            *entry = entry.checked_add(amount).ok_or_else(overflow)?;
+        }
+    }
+    
+    Ok((withdrawable, pending))
+}
+
+fn pending_withdrawals(pending: BTreeMap<Epoch, Amount>) -> Vec<PendingWithdrawal> {
+    pending.into_iter().map(|(epoch, amount)| PendingWithdrawal {
+        withdraw_epoch: epoch.0,
+        amount: amount.to_string(),
+    }).collect()
+}
+
+/// Get tokens withdrawable from a validator now and in future epochs
+/// 
+/// # Endpoint
+/// `GET /api/pos/withdrawable/{source}/{validator}?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `source`: Namada address of the bond owner (the validator itself for self-bonds)
+/// - `validator`: Namada address of the validator
+/// - `epoch`: Optional epoch to compute the schedule at (default: current epoch)
+/// 
+/// # Response
+/// ```json
+/// {
+///     "source": "tnam1q...",
+///     "validator": "tnam1q...",
+///     "epoch": 120,
+///     "withdrawable": "250000",
+///     "pending": [
+///         {
+///             "withdraw_epoch": 125,
+///             "amount": "500000"
+///         }
+///     ]
+/// }
+/// ```
+async fn get_withdrawable(
+    state: Arc<AppState>,
+    source: String,
+    validator: String,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    // Validate address formats
+    let source_addr = Address::from_str(&source)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid source address: {}", e))))?;
+    let validator_addr = Address::from_str(&validator)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid validator address: {}", e))))?;
+    
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let details = state.namada_client.get_bonds_and_unbonds(Some(&source_addr), Some(&validator_addr)).await?;
+    
+    let (withdrawable, pending) = withdrawal_schedule(
+        details.values().flat_map(|detail| &detail.unbonds),
+        epoch,
+    )?;
+    
+    Ok(warp::reply::json(&WithdrawableResponse {
+        source,
+        validator,
+        epoch: epoch.0,
+        withdrawable: withdrawable.to_string(),
+        pending: pending_withdrawals(pending),
+    }))
+}
+
+/// Get tokens withdrawable from all validators of a source, now and in future epochs
+/// 
+/// # Endpoint
+/// `GET /api/pos/withdrawable/{source}?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `source`: Namada address of the bond owner
+/// - `epoch`: Optional epoch to compute the schedule at (default: current epoch)
+/// 
+/// # Response
+/// ```json
+/// {
+///     "source": "tnam1q...",
+///     "epoch": 120,
+///     "withdrawable": "250000",
+///     "pending": [
+///         {
+///             "withdraw_epoch": 125,
+///             "amount": "500000"
+///         }
+///     ],
+///     "validators": [
+///         {
+///             "validator": "tnam1q...",
+///             "withdrawable": "250000",
+///             "pending": [
+///                 {
+///                     "withdraw_epoch": 125,
+///                     "amount": "500000"
+///                 }
+///             ]
+///         }
+///     ]
+/// }
+/// ```
+async fn get_source_withdrawable(
+    state: Arc<AppState>,
+    source: String,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    // Validate address format
+    let source_addr = Address::from_str(&source)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid source address: {}", e))))?;
+    
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let details = state.namada_client.get_bonds_and_unbonds(Some(&source_addr), None).await?;
+    
+    let (withdrawable, pending) = withdrawal_schedule(
+        details.values().flat_map(|detail| &detail.unbonds),
+        epoch,
+    )?;
+    
+    // Break the totals down per validator, since withdrawals are made per validator
+    let mut validators = Vec::new();
+    for (bond_id, detail) in details.iter().filter(|(_, detail)| !detail.unbonds.is_empty()) {
+        let (validator_withdrawable, validator_pending) = withdrawal_schedule(&detail.unbonds, epoch)?;
+        validators.push(ValidatorWithdrawable {
+            validator: bond_id.validator.to_string(),
+            withdrawable: validator_withdrawable.to_string(),
+            pending: pending_withdrawals(validator_pending),
+        });
+    }
+    validators.sort_by(|a, b| a.validator.cmp(&b.validator));
+    
+    Ok(warp::reply::json(&SourceWithdrawableResponse {
+        source,
+        epoch: epoch.0,
+        withdrawable: withdrawable.to_string(),
+        pending: pending_withdrawals(pending),
+        validators,
+    }))
+}
+
+/// Get unclaimed staking rewards at a validator
+/// 
+/// # Endpoint
+/// `GET /api/pos/rewards/{validator}/{delegator}?epoch={epoch}`
+/// `GET /api/pos/rewards/{validator}?epoch={epoch}` (validator's self-bond rewards)
+/// 
+/// # Parameters
+/// - `validator`: Namada address of the validator
+/// - `delegator`: Optional Namada address of the delegator
+/// - `epoch`: Optional epoch (default: current epoch)
+/// 
+/// # Response
+/// ```json
+/// {
+///     "validator": "tnam1q...",
+///     "delegator": "tnam1q...",
+///     "epoch": 120,
+///     "rewards": "1500"
+/// }
+/// ```
+async fn get_rewards(
+    state: Arc<AppState>,
+    validator: String,
+    delegator: Option<String>,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    // Validate address formats
+    let validator_addr = Address::from_str(&validator)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid validator address: {}", e))))?;
+    let delegator_addr = delegator.as_deref()
+        .map(Address::from_str)
+        .transpose()
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid delegator address: {}", e))))?;
+    
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let rewards = state.namada_client.query_rewards(&validator_addr, delegator_addr.as_ref(), Some(epoch)).await?;
+    
+    Ok(warp::reply::json(&RewardsResponse {
+        validator,
+        delegator,
+        epoch: epoch.0,
+        rewards: rewards.to_string(),
+    }))
+}
+
+/// Get unclaimed staking rewards of a delegator across all its validators
+/// 
+/// Only the validators the delegator has a bond with at `epoch` are included.
+/// Rewards left unclaimed at a validator it fully unbonded from are not part
+/// of the total, they are returned by `GET /api/pos/rewards/{validator}/{delegator}`.
+/// 
+/// # Endpoint
+/// `GET /api/pos/total_rewards/{delegator}?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `delegator`: Namada address of the delegator
+/// - `epoch`: Optional epoch (default: current epoch)
+/// 
+/// # Response
+/// ```json
+/// {
+///     "delegator": "tnam1q...",
+///     "epoch": 120,
+///     "total": "2500",
+///     "validators": [
+///         {
+///             "validator": "tnam1q...",
+///             "rewards": "1500"
+///         }
+///     ]
+/// }
+/// ```
+async fn get_total_rewards(
+    state: Arc<AppState>,
+    delegator: String,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    // Validate address format
+    let delegator_addr = Address::from_str(&delegator)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid delegator address: {}", e))))?;
+    
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let mut validators = state.namada_client.get_delegation_validators(&delegator_addr, Some(epoch)).await?;
+    validators.sort_by_key(|validator| validator.to_string());
+    
+    // Query the rewards at each validator concurrently, keeping their order,
+    // with at most `validators_details_concurrency` validators in flight
+    let rewards = futures::stream::iter(validators.iter().cloned())
+        .map(|validator| {
+            let (state, delegator_addr) = (&state, &delegator_addr);
+            async move { state.namada_client.query_rewards(&validator, Some(delegator_addr), Some(epoch)).await }
+        })
+        .buffered(state.validators_details_concurrency)
+        .try_collect::<Vec<_>>().await?;
+    
+    let total = rewards.iter()
+        .try_fold(Amount::zero(), |total, amount| total.checked_add(*amount))
+        .ok_or_else(|| warp::reject::custom(ApiError::InternalError("Rewards total overflow".to_string())))?;
+    
+    Ok(warp::reply::json(&TotalRewardsResponse {
+        delegator,
+        epoch: epoch.0,
+        total: total.to_string(),
+        validators: validators.into_iter().zip(rewards).map(|(validator, amount)| ValidatorRewards {
+            validator: validator.to_string(),
+            rewards: amount.to_string(),
+        }).collect(),
+    }))
+}
+
+fn slash_response(validator: &Address, slash: Slash) -> SlashResponse {
+    SlashResponse {
+        validator: validator.to_string(),
+        epoch: slash.epoch.0,
+        block_height: slash.block_height,
+        slash_type: slash_type_name(&slash.r#type),
+        rate: slash.rate.to_string(),
+    }
+}
+
+/// Get the slashes applied to a validator
+/// 
+/// # Endpoint
+/// `GET /api/pos/validators/{address}/slashes?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `address`: Namada address of the validator
+/// - `epoch`: Optional epoch, up to the pipeline epoch (default: current epoch).
+///   Only the slashes of infractions up to this epoch are returned.
+/// 
+/// # Response
+/// ```json
+/// {
+///     "address": "tnam1q...",
+///     "epoch": 120,
+///     "last_infraction_epoch": 95,
+///     "slashes": [
+///         {
+///             "validator": "tnam1q...",
+///             "epoch": 95,
+///             "block_height": 123456,
+///             "slash_type": "duplicate_vote",
+///             "rate": "0.001"
+///         }
+///     ]
+/// }
+/// ```
+async fn get_validator_slashes(
+    state: Arc<AppState>,
+    address: String,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    // Validate address format
+    let address = Address::from_str(&address)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid address format: {}", e))))?;
+    
+    // Check if address is a validator
+    let is_validator = state.namada_client.is_validator(&address).await?;
+    
+    if !is_validator {
+        return Err(warp::reject::custom(ApiError::NotFound(format!("Address {} is not a validator", address))));
+    }
+    
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let mut slashes = state.namada_client.get_validator_slashes(&address).await?;
+    slashes.retain(|slash| slash.epoch <= epoch);
+    slashes.sort_by_key(|slash| (slash.epoch, slash.block_height));
+    
+    // A later infraction is replaced by the last one up to the epoch
+    let last_infraction_epoch = state.namada_client.query_last_infraction_epoch(&address).await?
+        .filter(|last| *last <= epoch)
+        .or_else(|| slashes.last().map(|slash| slash.epoch));
+    
+    Ok(warp::reply::json(&ValidatorSlashesResponse {
+        address: address.to_string(),
+        epoch: epoch.0,
+        last_infraction_epoch: last_infraction_epoch.map(|epoch| epoch.0),
+        slashes: slashes.into_iter().map(|slash| slash_response(&address, slash)).collect(),
+    }))
+}
+
+/// Get the slashes applied to all validators
+/// 
+/// # Endpoint
+/// `GET /api/pos/slashes?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `epoch`: Optional epoch, up to the pipeline epoch (default: current epoch).
+///   Only the slashes of infractions up to this epoch are returned.
+/// 
+/// # Response
+/// ```json
+/// {
+///     "epoch": 120,
+///     "slashes": [
+///         {
+///             "validator": "tnam1q...",
+///             "epoch": 95,
+///             "block_height": 123456,
+///             "slash_type": "light_client_attack",
+///             "rate": "0.001"
+///         }
+///     ]
+/// }
+/// ```
+async fn get_all_slashes(state: Arc<AppState>, query: EpochQuery) -> Result<impl Reply, Rejection> {
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let all_slashes = state.namada_client.get_all_slashes().await?;
+    
+    let mut slashes: Vec<SlashResponse> = all_slashes.into_iter()
+        .flat_map(|(validator, slashes)| {
+            slashes.into_iter()
+                .filter(|slash| slash.epoch <= epoch)
+                .map(move |slash| slash_response(&validator, slash))
+        })
+        .collect();
+    slashes.sort_by(|a, b| (a.epoch, a.block_height, &a.validator).cmp(&(b.epoch, b.block_height, &b.validator)));
+    
+    Ok(warp::reply::json(&SlashesResponse {
+        epoch: epoch.0,
+        slashes,
+    }))
+}
+
+/// Fraction of the total supply that is bonded. Zero when there is no supply.
+pub fn staking_ratio(total_stake: Amount, total_supply: Amount) -> Option<Dec> {
+    if total_supply.is_zero() {
+        return Some(Dec::zero());
+    }
+    Dec::try_from(total_stake).ok()?
+        .checked_div(Dec::try_from(total_supply).ok()?)
+}
+
+/// Get total bonded stake and the staking ratio
+/// 
+/// # Endpoint
+/// `GET /api/pos/total_stake?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `epoch`: Optional epoch for the bonded stake (default: current epoch). The
+///   total supply is the one of the last committed block.
+/// 
+/// # Response
+/// ```json
+/// {
+///     "epoch": 120,
+///     "total_stake": "400000000",
+///     "native_token": "tnam1q...",
+///     "total_supply": "1000000000",
+///     "staking_ratio": "0.4"
+/// }
+/// ```
+async fn get_total_stake(
+    state: Arc<AppState>,
+    query: EpochQuery,
+) -> Result<impl Reply, Rejection> {
+    let epoch = resolve_epoch(&state, query.epoch).await?;
+    
+    let total_stake = state.namada_client.get_total_stake(Some(epoch)).await?;
+    
+    let native_token = state.namada_client.query_native_token().await?;
+    
+    let total_supply = state.namada_client.get_token_total_supply(&native_token, None).await?;
+    
+    let ratio = staking_ratio(total_stake, total_supply)
+        .ok_or_else(|| warp::reject::custom(ApiError::InternalError("Failed to compute staking ratio".to_string())))?;
+    
+    Ok(warp::reply::json(&TotalStakeResponse {
+        epoch: epoch.0,
+        total_stake: total_stake.to_string(),
+        native_token: native_token.to_string(),
+        total_supply: total_supply.to_string(),
+        staking_ratio: ratio.to_string(),
+    }))
+}
+
+/// Get the PoS parameters
+/// 
+/// # Endpoint
+/// `GET /api/pos/parameters?epoch={epoch}`
+/// 
+/// # Parameters
+/// - `epoch`: Optional epoch, which must be the current one, as the node only
+///   serves the parameters of its last block
+/// 
+/// # Response
+/// `min_self_bond` is the self-bond a validator without delegations needs to
+/// be active, which is the stake threshold, as Namada has no other minimum.
+/// ```json
+/// {
+///     "max_validator_slots": 257,
+///     "pipeline_len": 2,
+///     "unbonding_len": 21,
+///     "tm_votes_per_token": "0.1",
+///     "block_proposer_reward": "0.125",
+///     "block_vote_reward": "0.1",
+///     "max_inflation_rate": "0.05",
+///     "target_staked_ratio": "0.4",
+///     "duplicate_vote_min_slash_rate": "0.001",
+///     "light_client_attack_min_slash_rate": "0.001",
+///     "cubic_slashing_window_length": 1,
+///     "withdrawable_epoch_offset": 24,
+///     "validator_stake_threshold": "1000000",
+///     "min_self_bond": "1000000",
+///     "liveness_window_check": 10000,
+///     "liveness_threshold": "0.9",
+///     "rewards_gain_p": "0.25",
+///     "rewards_gain_d": "0.25",
+///     "max_proposal_period": 27
+/// }
+/// ```
+async fn get_pos_parameters(state: Arc<AppState>, query: EpochQuery) -> Result<impl Reply, Rejection> {
+    require_current_epoch(&state, query.epoch).await?;
+    
+    let params = state.namada_client.get_pos_params().await?;
+    let owned = params.owned;
+    
+    Ok(warp::reply::json(&PosParametersResponse {
+        max_validator_slots: owned.max_validator_slots,
+        pipeline_len: owned.pipeline_len,
+        unbonding_len: owned.unbonding_len,
+        tm_votes_per_token: owned.tm_votes_per_token.to_string(),
+        block_proposer_reward: owned.block_proposer_reward.to_string(),
+        block_vote_reward: owned.block_vote_reward.to_string(),
+        max_inflation_rate: owned.max_inflation_rate.to_string(),
+        target_staked_ratio: owned.target_staked_ratio.to_string(),
+        duplicate_vote_min_slash_rate: owned.duplicate_vote_min_slash_rate.to_string(),
+        light_client_attack_min_slash_rate: owned.light_client_attack_min_slash_rate.to_string(),
+        cubic_slashing_window_length: owned.cubic_slashing_window_length,
+        withdrawable_epoch_offset: owned.withdrawable_epoch_offset(),
+        validator_stake_threshold: owned.validator_stake_threshold.to_string(),
+        min_self_bond: owned.validator_stake_threshold.to_string(),
+        liveness_window_check: owned.liveness_window_check,
+        liveness_threshold: owned.liveness_threshold.to_string(),
+        rewards_gain_p: owned.rewards_gain_p.to_string(),
+        rewards_gain_d: owned.rewards_gain_d.to_string(),
+        max_proposal_period: params.max_proposal_period,
+    }))
+}
+
+/// Get token balance
+/// 
+/// # Endpoint
+/// `GET /api/token/balance?token={token}&owner={owner}&height={height}`
+/// 
+/// # Parameters
+/// - `token`: Token address
 /// - `owner`: Owner address
-/// - `height`: Optional block height
+/// - `height`: Optional block height (default: last committed block)
 /// 
 /// # Response
 /// ```json
@@ -663,51 +1939,54 @@ async fn get_token_balance(
     let owner = Address::from_str(&query.owner)
         .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid owner address: {}", e))))?;
     
-    // Convert height if provided
-    let height = query.height.map(|h| BlockHeight(h));
+    let height = resolve_height(&state, query.height).await?;
     
     // Query balance
-    let balance = state.namada_client.get_token_balance(&token, &owner, height).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+    let balance = state.namada_client.get_token_balance(&token, &owner, Some(height)).await?;
     
     Ok(warp::reply::json(&TokenBalanceResponse {
         token: query.token,
         owner: query.owner,
         balance: balance.to_string(),
-        height: query.height,
+        height: height.0,
     }))
 }
 
 /// Get token total supply
 /// 
 /// # Endpoint
-/// `GET /api/token/total_supply/{token}`
+/// `GET /api/token/total_supply/{token}?height={height}`
 /// 
 /// # Parameters
 /// - `token`: Token address
+/// - `height`: Optional block height (default: last committed block)
 /// 
 /// # Response
 /// ```json
 /// {
 ///     "token": "tnam1q...",
-///     "total_supply": "1000000000"
+///     "total_supply": "1000000000",
+///     "height": 12345
 /// }
 /// ```
 async fn get_token_total_supply(
     state: Arc<AppState>,
     token: String,
+    query: HeightQuery,
 ) -> Result<impl Reply, Rejection> {
     // Parse token address
     let token_addr = Address::from_str(&token)
         .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid token address: {}", e))))?;
     
+    let height = resolve_height(&state, query.height).await?;
+    
     // Query total supply
-    let total_supply = state.namada_client.get_token_total_supply(&token_addr).await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+    let total_supply = state.namada_client.get_token_total_supply(&token_addr, Some(height)).await?;
     
     Ok(warp::reply::json(&TokenTotalSupplyResponse {
         token,
         total_supply: total_supply.to_string(),
+        height: height.0,
     }))
 }
 
@@ -724,8 +2003,7 @@ async fn get_token_total_supply(
 /// ```
 async fn get_native_token(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
     // Query native token address
-    let native_token = state.namada_client.query_native_token().await
-        .map_err(|e| warp::reject::custom(ApiError::QueryError(e.to_string())))?;
+    let native_token = state.namada_client.query_native_token().await?;
     
     Ok(warp::reply::json(&NativeTokenResponse {
         address: native_token.to_string(),
@@ -757,4 +2035,593 @@ async fn serve_docs() -> Result<impl Reply, Rejection> {
     };
     
     Ok(warp::reply::html(docs_content))
-} 
\ No newline at end of file
+} 
+
+/// Number of proposals queried concurrently when listing proposals
+const GOV_PROPOSALS_CONCURRENCY: usize = 10;
+
+/// Get governance proposals, newest first
+/// 
+/// # Endpoint
+/// `GET /api/gov/proposals?status={status}&page={page}&per_page={per_page}`
+/// 
+/// # Parameters
+/// - `status`: Optional status filter, `pending`, `voting` or `ended`
+/// - `page`: Page number (default: 1)
+/// - `per_page`: Items per page (default: 10, max: 50)
+/// 
+/// # Response
+/// ```json
+/// {
+///     "epoch": 120,
+///     "proposals": [
+///         {
+///             "id": 42,
+///             "title": "Increase the validator slots",
+///             "type": "default",
+///             "author": "tnam1q...",
+///             "voting_start_epoch": 118,
+///             "voting_end_epoch": 124,
+///             "activation_epoch": 130,
+///             "status": "voting"
+///         }
+///     ],
+///     "pagination": {
+///         "total": 43,
+///         "page": 1,
+///         "per_page": 10,
+///         "total_pages": 5
+///     }
+/// }
+/// ```
+async fn get_proposals(
+    state: Arc<AppState>,
+    query: ProposalsQuery,
+) -> Result<impl Reply, Rejection> {
+    let status = query.validate()?;
+    let page = query.page.unwrap_or(1);
+    let per_page = query.per_page.unwrap_or(10);
+    
+    let epoch = resolve_epoch(&state, None).await?;
+    
+    let counter = state.namada_client.get_proposal_counter().await?;
+    
+    // Proposals are cached once fetched, so only the new ones reach the RPC
+    let proposals = futures::stream::iter((0..counter).rev())
+        .map(|id| state.namada_client.get_proposal(id))
+        .buffered(GOV_PROPOSALS_CONCURRENCY)
+        .try_collect::<Vec<_>>().await?;
+    let proposals: Vec<_> = proposals.iter()
+        .flatten()
+        .map(|proposal| ProposalSummaryResponse::new(proposal, epoch))
+        .filter(|proposal| status.is_none_or(|status| proposal.status == status))
+        .collect();
+    
+    let total = proposals.len();
+    let total_pages = total.div_ceil(per_page as usize) as u32;
+    if page > total_pages && total_pages > 0 {
+        return Err(warp::reject::custom(ApiError::InvalidPagination(
+            format!("Page number {} exceeds total pages {}", page, total_pages)
+        )));
+    }
+    
+    let start = ((page - 1) * per_page) as usize;
+    Ok(warp::reply::json(&ProposalsResponse {
+        epoch: epoch.0,
+        proposals: proposals.into_iter().skip(start).take(per_page as usize).collect(),
+        pagination: PaginationResponse {
+            total,
+            page,
+            per_page,
+            total_pages,
+        },
+    }))
+}
+
+/// Get a governance proposal with its current tally
+/// 
+/// # Endpoint
+/// `GET /api/gov/proposals/{id}`
+/// 
+/// # Parameters
+/// - `id`: Proposal ID
+/// 
+/// # Response
+/// ```json
+/// {
+///     "epoch": 120,
+///     "id": 42,
+///     "content": {
+///         "title": "Increase the validator slots",
+///         "details": "..."
+///     },
+///     "type": "default",
+///     "data": null,
+///     "author": "tnam1q...",
+///     "voting_start_epoch": 118,
+///     "voting_end_epoch": 124,
+///     "activation_epoch": 130,
+///     "status": "voting",
+///     "tally": {
+///         "result": "passed",
+///         "tally_type": "two_fifths",
+///         "total_voting_power": "1000000",
+///         "total_yay_power": "600000",
+///         "total_nay_power": "100000",
+///         "total_abstain_power": "0"
+///     }
+/// }
+/// ```
+async fn get_proposal(
+    state: Arc<AppState>,
+    id: u64,
+) -> Result<impl Reply, Rejection> {
+    let epoch = resolve_epoch(&state, None).await?;
+    
+    let proposal = state.namada_client.get_proposal(id).await?
+        .ok_or_else(|| warp::reject::custom(ApiError::NotFound(format!("Proposal {} not found", id))))?;
+    
+    let tally = state.namada_client.get_proposal_result(id).await?;
+    
+    Ok(warp::reply::json(&ProposalResponse::new(epoch, proposal, tally)))
+}
+
+/// Get the votes cast on a governance proposal
+/// 
+/// # Endpoint
+/// `GET /api/gov/proposals/{id}/votes`
+/// 
+/// # Parameters
+/// - `id`: Proposal ID
+/// 
+/// # Response
+/// ```json
+/// {
+///     "epoch": 120,
+///     "proposal_id": 42,
+///     "votes": [
+///         {
+///             "voter": "tnam1q...",
+///             "validator": "tnam1q...",
+///             "vote": "yay",
+///             "voter_type": "validator"
+///         }
+///     ]
+/// }
+/// ```
+async fn get_proposal_votes(
+    state: Arc<AppState>,
+    id: u64,
+) -> Result<impl Reply, Rejection> {
+    let epoch = resolve_epoch(&state, None).await?;
+    
+    state.namada_client.get_proposal(id).await?
+        .ok_or_else(|| warp::reject::custom(ApiError::NotFound(format!("Proposal {} not found", id))))?;
+    
+    let mut votes: Vec<VoteResponse> = state.namada_client.get_proposal_votes(id).await?
+        .iter()
+        .map(VoteResponse::from)
+        .collect();
+    
+    // Keep the output stable regardless of the order the node returns votes in
+    votes.sort_by(|a, b| (&a.voter, &a.validator).cmp(&(&b.voter, &b.validator)));
+    
+    Ok(warp::reply::json(&ProposalVotesResponse {
+        epoch: epoch.0,
+        proposal_id: id,
+        votes,
+    }))
+}
+
+/// Get how the stake of an address votes on a governance proposal, for each
+/// validator it delegates to. The address inherits the vote of the validators
+/// it did not vote through itself.
+/// 
+/// # Endpoint
+/// `GET /api/gov/proposals/{id}/votes/{address}`
+/// 
+/// # Parameters
+/// - `id`: Proposal ID
+/// - `address`: Validator or delegator address
+/// 
+/// # Response
+/// `epoch` is the epoch of the delegations: the current one while voting, the
+/// last epoch of voting once ended.
+/// ```json
+/// {
+///     "epoch": 120,
+///     "proposal_id": 42,
+///     "address": "tnam1q...",
+///     "voted": false,
+///     "votes": [
+///         {
+///             "validator": "tnam1q...",
+///             "vote": "nay",
+///             "inherited": true
+///         }
+///     ]
+/// }
+/// ```
+async fn get_effective_vote(
+    state: Arc<AppState>,
+    id: u64,
+    address: String,
+) -> Result<impl Reply, Rejection> {
+    // Validate address format
+    let address = Address::from_str(&address)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid address format: {}", e))))?;
+    
+    let epoch = resolve_epoch(&state, None).await?;
+    
+    let proposal = state.namada_client.get_proposal(id).await?
+        .ok_or_else(|| warp::reject::custom(ApiError::NotFound(format!("Proposal {} not found", id))))?;
+    
+    let votes = state.namada_client.get_proposal_votes(id).await?;
+    
+    // Votes are tallied with the delegations of the last epoch of voting
+    let tally_epoch = epoch.min(proposal.voting_end_epoch);
+    let validators = state.namada_client.get_delegation_validators(&address, Some(tally_epoch)).await?;
+    
+    let (voted, mut effective_votes) = EffectiveVote::of(&address, &votes, &validators);
+    effective_votes.sort_by(|a, b| a.validator.cmp(&b.validator));
+    
+    Ok(warp::reply::json(&EffectiveVoteResponse {
+        epoch: tally_epoch.0,
+        proposal_id: id,
+        address: address.to_string(),
+        voted,
+        votes: effective_votes,
+    }))
+}
+
+/// Get governance parameters
+/// 
+/// # Endpoint
+/// `GET /api/gov/parameters`
+/// 
+/// # Response
+/// ```json
+/// {
+///     "min_proposal_fund": "500000000",
+///     "max_proposal_code_size": 600000,
+///     "min_proposal_voting_period": 3,
+///     "max_proposal_period": 27,
+///     "max_proposal_content_size": 10000,
+///     "min_proposal_grace_epochs": 6,
+///     "max_proposal_latency": 30
+/// }
+/// ```
+async fn get_gov_parameters(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
+    let params = state.namada_client.get_gov_params().await?;
+    
+    Ok(warp::reply::json(&GovParametersResponse {
+        min_proposal_fund: params.min_proposal_fund.to_string(),
+        max_proposal_code_size: params.max_proposal_code_size,
+        min_proposal_voting_period: params.min_proposal_voting_period,
+        max_proposal_period: params.max_proposal_period,
+        max_proposal_content_size: params.max_proposal_content_size,
+        min_proposal_grace_epochs: params.min_proposal_grace_epochs,
+        max_proposal_latency: params.max_proposal_latency,
+    }))
+}
+
+/// Get the last committed block
+/// 
+/// # Endpoint
+/// `GET /api/blocks/latest`
+/// 
+/// # Response
+/// Same as `GET /api/blocks/{height}`
+async fn get_latest_block(state: Arc<AppState>) -> Result<impl Reply, Rejection> {
+    let height = resolve_height(&state, None).await?;
+    block_response(&state, height).await
+}
+
+/// Get a block, with its proposer and transaction hashes
+/// 
+/// # Endpoint
+/// `GET /api/blocks/{height}`
+/// 
+/// # Parameters
+/// - `height`: Block height, up to the last committed one
+/// 
+/// # Response
+/// ```json
+/// {
+///     "height": 12345,
+///     "hash": "4A3B...",
+///     "time": "2024-05-01T12:00:00Z",
+///     "chain_id": "namada.5f5de2dd1b88cba30586420",
+///     "last_block_hash": "9C1D...",
+///     "app_hash": "E3B0...",
+///     "data_hash": "6F2A...",
+///     "validators_hash": "B1C2...",
+///     "proposer_address": "CAFAD8DA813BAE48779A4219A74632D5DCA49737",
+///     "proposer": "tnam1q...",
+///     "tx_count": 1,
+///     "tx_hashes": ["5D8E..."]
+/// }
+/// ```
+async fn get_block(state: Arc<AppState>, height: u64) -> Result<impl Reply, Rejection> {
+    let height = resolve_height(&state, Some(height)).await?;
+    block_response(&state, height).await
+}
+
+async fn block_response(state: &AppState, height: BlockHeight) -> Result<warp::reply::Json, Rejection> {
+    let block = state.namada_client.get_block(height).await?;
+    
+    // Consensus keys may have changed since, the proposer is looked up with the current ones
+    let proposer = state.namada_client.validator_by_tm_addr(block.block.header.proposer_address.to_string()).await?;
+    
+    Ok(warp::reply::json(&BlockResponse::new(&block.block_id, &block.block, proposer)))
+}
+
+/// Get the signatures of a block: whether each validator of its set signed
+/// it, with its voting power
+/// 
+/// # Endpoint
+/// `GET /api/blocks/{height}/signatures`
+/// 
+/// # Parameters
+/// - `height`: Block height, up to the last committed one
+/// 
+/// # Response
+/// `status` is `signed`, `absent` or `nil`. The commit of the last block only
+/// holds the signatures seen so far, and is not `canonical`.
+/// ```json
+/// {
+///     "height": 12345,
+///     "canonical": true,
+///     "total_voting_power": 1000000,
+///     "signed_voting_power": 950000,
+///     "signatures": [
+///         {
+///             "validator": "tnam1q...",
+///             "consensus_address": "CAFAD8DA813BAE48779A4219A74632D5DCA49737",
+///             "status": "signed",
+///             "voting_power": 50000
+///         }
+///     ]
+/// }
+/// ```
+async fn get_block_signatures(state: Arc<AppState>, height: u64) -> Result<impl Reply, Rejection> {
+    let height = resolve_height(&state, Some(height)).await?;
+    
+    let (commit, validators, liveness_info) = futures::try_join!(
+        state.namada_client.get_commit(height),
+        state.namada_client.get_comet_validators(height),
+        state.namada_client.get_liveness_info(),
+    )?;
+    
+    // The liveness info maps the current consensus validators, the others are looked up one by one
+    let mut addresses: HashMap<String, Address> = liveness_info.validators.into_iter()
+        .map(|validator| (validator.comet_address, validator.native_address))
+        .collect();
+    let unknown: Vec<String> = validators.iter()
+        .map(|validator| validator.address.to_string())
+        .filter(|tm_addr| !addresses.contains_key(tm_addr))
+        .collect();
+    let found = futures::stream::iter(unknown)
+        .map(|tm_addr| async {
+            let address = state.namada_client.validator_by_tm_addr(tm_addr.clone()).await?;
+            Ok::<_, client::ClientError>(address.map(|address| (tm_addr, address)))
+        })
+        .buffered(state.validators_details_concurrency)
+        .try_collect::<Vec<_>>().await?;
+    addresses.extend(found.into_iter().flatten());
+    
+    Ok(warp::reply::json(&BlockSignaturesResponse::new(
+        height.0,
+        commit.canonical,
+        &validators,
+        &commit.signed_header.commit.signatures,
+        &addresses,
+    )))
+}
+
+/// Get an applied transaction, with the type and status of its inner
+/// transactions and its events
+/// 
+/// # Endpoint
+/// `GET /api/tx/{hash}`
+/// 
+/// # Parameters
+/// - `hash`: Transaction hash, the hash of its header (64 hex characters)
+/// 
+/// # Response
+/// Transactions are looked up in the event log of the node, which only keeps
+/// recent blocks.
+/// ```json
+/// {
+///     "hash": "5D8E...",
+///     "height": 12345,
+///     "index": 0,
+///     "type": "wrapper",
+///     "code": 0,
+///     "gas_used": 27000,
+///     "info": "",
+///     "log": "",
+///     "inner_txs": [
+///         {
+///             "hash": "A1F0...",
+///             "type": "tx_bond",
+///             "status": "accepted"
+///         }
+///     ],
+///     "events": [
+///         {
+///             "type": "token/transfer",
+///             "attributes": {
+///                 "inner-tx-hash": "A1F0..."
+///             }
+///         }
+///     ]
+/// }
+/// ```
+async fn get_tx(state: Arc<AppState>, hash: String) -> Result<impl Reply, Rejection> {
+    let hash = namada_core::hash::Hash::from_str(&hash)
+        .map_err(|_| warp::reject::custom(ApiError::BadRequest(format!(
+            "Invalid transaction hash: {}. Expected 64 hex characters.", hash
+        ))))?;
+    
+    let events = state.namada_client.get_tx_events(&hash).await?
+        .ok_or_else(|| warp::reject::custom(ApiError::NotFound(format!("Transaction {} not found", hash))))?;
+    
+    let height = events.applied.read_attribute::<namada_sdk::events::extend::Height>()
+        .map_err(|e| warp::reject::custom(ApiError::QueryError(format!("Malformed event of transaction {}: {}", hash, e))))?;
+    let block = state.namada_client.get_block(height).await?;
+    
+    let (index, tx) = block.block.data.iter()
+        .enumerate()
+        .find_map(|(index, bytes)| {
+            let tx = namada_sdk::tx::Tx::try_from(bytes.as_slice()).ok()?;
+            (tx.header_hash() == hash).then_some((index, tx))
+        })
+        .ok_or_else(|| warp::reject::custom(ApiError::QueryError(format!(
+            "Transaction {} is missing from block {}", hash, height
+        ))))?;
+    
+    Ok(warp::reply::json(&TxResponse::new(&tx, index, events)?))
+}
+
+/// Subscribe to chain events over a WebSocket
+/// 
+/// # Endpoint
+/// `GET /api/ws`
+/// 
+/// # Parameters
+/// - `topics` (optional): Comma-separated topics subscribed to on connection:
+///   `new_block`, `new_epoch`, `validator_set_change` or `liveness_update`
+/// 
+/// # Messages
+/// Clients change their topics with
+/// `{"action": "subscribe", "topics": ["new_block"]}` or
+/// `{"action": "unsubscribe", "topics": ["new_block"]}`, answered with the
+/// topics they are subscribed to. Events are sent as they happen:
+/// ```json
+/// {"type": "subscribed", "topics": ["new_block", "new_epoch"]}
+/// {"type": "event", "topic": "new_epoch", "data": {"epoch": 42, "height": 12345}}
+/// {"type": "error", "message": "Too slow, 12 events were dropped"}
+/// ```
+async fn ws_connect(state: Arc<AppState>, ws: warp::ws::Ws, query: WsQuery) -> Result<impl Reply, Rejection> {
+    let topics = query.topics.as_deref().unwrap_or_default()
+        .split(',')
+        .filter(|topic| !topic.is_empty())
+        .map(Topic::from_str)
+        .collect::<Result<BTreeSet<_>, _>>()?;
+    
+    let events = state.events.subscribe();
+    Ok(ws.on_upgrade(move |socket| events::ws_session(socket, events, topics)))
+}
+
+/// Stream the epoch as server-sent events, an `epoch` event being sent
+/// whenever it advances
+/// 
+/// # Endpoint
+/// `GET /api/stream/epoch`
+/// 
+/// # Parameters
+/// - `Last-Event-ID` header (optional): Last epoch received. The current epoch
+///   is sent first, unless it is this one.
+/// 
+/// # Response
+/// Events have the epoch as ID, and the block it was seen at:
+/// ```text
+/// event: epoch
+/// id: 42
+/// data: {"epoch":42,"height":12345}
+/// ```
+async fn stream_epoch(state: Arc<AppState>, last_event_id: Option<u64>) -> Result<impl Reply, Rejection> {
+    // Subscribe first, not to miss an epoch change while querying the current epoch
+    let events = state.events.subscribe();
+    let height = state.namada_client.query_last_height().await?;
+    let epoch = resolve_epoch(&state, None).await?;
+    
+    let stream = events::new_epochs(NewEpochEvent { epoch: epoch.0, height: height.0 }, last_event_id, events)
+        .map(|new_epoch| warp::sse::Event::default()
+            .event("epoch")
+            .id(new_epoch.epoch.to_string())
+            .json_data(&new_epoch));
+    Ok(warp::sse::reply(warp::sse::keep_alive().stream(stream)))
+}
+
+/// Stream the state of a validator as server-sent events, a `validator`
+/// event being sent whenever its state, stake or commission changes. These
+/// only change with the epoch, so the validator is queried once per epoch by
+/// the events poller, for all its streams.
+/// 
+/// # Endpoint
+/// `GET /api/stream/validators/{address}`
+/// 
+/// # Parameters
+/// - `address`: Namada address of the validator
+/// - `Last-Event-ID` header (optional): ID of the last event received. The
+///   current state is sent first, unless it is known to be the same as at
+///   that event.
+/// 
+/// # Response
+/// Events have the height the epoch was seen at as ID, and the same data as
+/// `GET /api/pos/validator_details/{address}`:
+/// ```text
+/// event: validator
+/// id: 12345
+/// data: {"address":"tnam1q...","epoch":42,"state":"Consensus","stake":"1000000",...}
+/// ```
+async fn stream_validator(state: Arc<AppState>, address: String, last_event_id: Option<u64>) -> Result<impl Reply, Rejection> {
+    let address = Address::from_str(&address)
+        .map_err(|e| warp::reject::custom(ApiError::InvalidAddress(format!("Invalid address format: {}", e))))?;
+    
+    let is_validator = state.namada_client.is_validator(&address).await?;
+    if !is_validator {
+        return Err(warp::reject::custom(ApiError::NotFound(format!("Address {} is not a validator", address))));
+    }
+    
+    // Subscribe first, not to miss an epoch while querying the current state
+    let snapshots = state.events.watch_validator(address.clone());
+    let height = state.namada_client.query_last_height().await?;
+    let current = validator_details_at(&state, &address, height).await?;
+    
+    // On resume, the current state is only sent if it changed since the last
+    // event, or if the state at that event can't be queried, e.g. pruned
+    let changed = match last_event_id {
+        Some(last_height) if last_height < height.0 => {
+            match validator_details_at(&state, &address, BlockHeight(last_height)).await {
+                Ok(last) => last.has_changed(&current),
+                Err(err) => {
+                    warn!("Failed to query validator {} at block {}, sending its current state: {:?}", address, last_height, err);
+                    true
+                }
+            }
+        }
+        Some(_) => false,
+        None => true,
+    };
+    
+    let first = changed.then(|| sse_validator_event(&current, height));
+    let mut last = current;
+    let updates = snapshots.filter_map(move |snapshot| {
+        // Snapshots of the poller may be older than the current state
+        let is_update = snapshot.details.epoch >= last.epoch && snapshot.details.has_changed(&last);
+        if is_update {
+            last = snapshot.details.clone();
+        }
+        futures::future::ready(is_update.then(|| sse_validator_event(&snapshot.details, BlockHeight(snapshot.height))))
+    });
+    Ok(warp::sse::reply(warp::sse::keep_alive().stream(futures::stream::iter(first).chain(updates))))
+}
+
+/// Query the state, stake and metadata of a validator at the epoch of a block
+async fn validator_details_at(state: &AppState, address: &Address, height: BlockHeight) -> Result<ValidatorDetailsResponse, ApiError> {
+    let epoch = state.namada_client.query_epoch_at_height(height).await?
+        .ok_or_else(|| ApiError::InvalidHeight(format!("The epoch of block height {} is unknown", height)))?;
+    validator_details(&state.namada_client, address.clone(), epoch).await
+}
+
+/// `validator` server-sent event, with the height of the block the state was
+/// queried at as ID
+fn sse_validator_event(details: &ValidatorDetailsResponse, height: BlockHeight) -> Result<warp::sse::Event, serde_json::Error> {
+    warp::sse::Event::default()
+        .event("validator")
+        .id(height.to_string())
+        .json_data(details)
+}
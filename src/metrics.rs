@@ -0,0 +1,158 @@
+use std::sync::LazyLock;
+use std::time::Duration;
+use prometheus::{
+    HistogramOpts, HistogramVec, IntCounterVec, IntGauge, IntGaugeVec, GaugeVec, Opts, Registry,
+    TextEncoder,
+};
+use crate::client::NamadaClient;
+
+/// Static path segments of the API routes. Any other segment is an address,
+/// a number or a hash, and is reported as `{param}` to bound the number of
+/// `route` label values.
+const ROUTE_SEGMENTS: &[&str] = &[
+    "api", "docs", "metrics",
+    "health", "api_status", "rpc_status", "cache_status",
+    "pos", "liveness_info", "validator_by_tm_addr", "validator_details", "validators",
+    "validators_details", "validator_set", "consensus", "below_capacity", "delegations",
+    "bonds", "unbonds", "to", "withdrawable", "rewards", "total_rewards", "slashes",
+    "total_stake", "parameters",
+    "token", "balance", "total_supply", "native",
+    "gov", "proposals", "votes",
+    "blocks", "latest", "signatures", "tx",
+    "stream", "epoch", "ws",
+];
+
+/// Prometheus metrics of the API
+pub struct Metrics {
+    registry: Registry,
+    http_requests: IntCounterVec,
+    http_request_duration: HistogramVec,
+    rpc_calls: IntCounterVec,
+    rpc_errors: IntCounterVec,
+    rpc_call_duration: HistogramVec,
+    cache_hits: IntCounterVec,
+    cache_misses: IntCounterVec,
+    cache_entries: IntGaugeVec,
+    cache_hit_ratio: GaugeVec,
+    chain_epoch: IntGauge,
+    chain_height: IntGauge,
+}
+
+/// Metrics of the process, shared by the server and the client
+pub static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new_custom(Some("namada_api".to_string()), None)
+            .expect("Invalid metrics prefix");
+        let metrics = Self {
+            http_requests: IntCounterVec::new(
+                Opts::new("http_requests_total", "HTTP requests served, by route, method and status code"),
+                &["route", "method", "status"],
+            ).unwrap(),
+            http_request_duration: HistogramVec::new(
+                HistogramOpts::new("http_request_duration_seconds", "HTTP request latency, by route, method and status code"),
+                &["route", "method", "status"],
+            ).unwrap(),
+            rpc_calls: IntCounterVec::new(
+                Opts::new("rpc_calls_total", "RPC calls, by client method"),
+                &["method"],
+            ).unwrap(),
+            rpc_errors: IntCounterVec::new(
+                Opts::new("rpc_errors_total", "RPC calls that failed on every endpoint, by client method"),
+                &["method"],
+            ).unwrap(),
+            rpc_call_duration: HistogramVec::new(
+                HistogramOpts::new("rpc_call_duration_seconds", "RPC call latency including failover, by client method"),
+                &["method"],
+            ).unwrap(),
+            cache_hits: IntCounterVec::new(
+                Opts::new("cache_hits_total", "Query cache hits, by query kind"),
+                &["kind"],
+            ).unwrap(),
+            cache_misses: IntCounterVec::new(
+                Opts::new("cache_misses_total", "Query cache misses, by query kind"),
+                &["kind"],
+            ).unwrap(),
+            cache_entries: IntGaugeVec::new(
+                Opts::new("cache_entries", "Query cache entries, by query kind"),
+                &["kind"],
+            ).unwrap(),
+            cache_hit_ratio: GaugeVec::new(
+                Opts::new("cache_hit_ratio", "Share of query cache lookups that hit, by query kind"),
+                &["kind"],
+            ).unwrap(),
+            chain_epoch: IntGauge::new("chain_epoch", "Last epoch observed on the chain").unwrap(),
+            chain_height: IntGauge::new("chain_height", "Highest block height reported by a healthy RPC endpoint").unwrap(),
+            registry,
+        };
+
+        let collectors: [Box<dyn prometheus::core::Collector>; 11] = [
+            Box::new(metrics.http_requests.clone()),
+            Box::new(metrics.http_request_duration.clone()),
+            Box::new(metrics.rpc_calls.clone()),
+            Box::new(metrics.rpc_errors.clone()),
+            Box::new(metrics.rpc_call_duration.clone()),
+            Box::new(metrics.cache_hits.clone()),
+            Box::new(metrics.cache_misses.clone()),
+            Box::new(metrics.cache_entries.clone()),
+            Box::new(metrics.cache_hit_ratio.clone()),
+            Box::new(metrics.chain_epoch.clone()),
+            Box::new(metrics.chain_height.clone()),
+        ];
+        for collector in collectors {
+            metrics.registry.register(collector).expect("Metric registered twice");
+        }
+        metrics
+    }
+
+    /// Record a served HTTP request
+    pub fn record_request(&self, path: &str, method: &str, status: u16, duration: Duration) {
+        let route = route_template(path);
+        let status = status.to_string();
+        let labels = [route.as_str(), method, status.as_str()];
+        self.http_requests.with_label_values(&labels).inc();
+        self.http_request_duration.with_label_values(&labels).observe(duration.as_secs_f64());
+    }
+
+    /// Record a call of a `NamadaClient` method to the RPC
+    pub fn record_rpc_call(&self, method: &str, duration: Duration, success: bool) {
+        self.rpc_calls.with_label_values(&[method]).inc();
+        if !success {
+            self.rpc_errors.with_label_values(&[method]).inc();
+        }
+        self.rpc_call_duration.with_label_values(&[method]).observe(duration.as_secs_f64());
+    }
+
+    /// Render the metrics in the Prometheus text format, after refreshing
+    /// the cache and chain metrics from the client
+    pub fn render(&self, client: &NamadaClient) -> String {
+        let stats = client.cache_stats();
+        for (kind, stats) in &stats.kinds {
+            let labels = [*kind];
+            let hits = self.cache_hits.with_label_values(&labels);
+            hits.inc_by(stats.hits.saturating_sub(hits.get()));
+            let misses = self.cache_misses.with_label_values(&labels);
+            misses.inc_by(stats.misses.saturating_sub(misses.get()));
+            self.cache_entries.with_label_values(&labels).set(stats.entries as i64);
+            self.cache_hit_ratio.with_label_values(&labels).set(stats.hit_ratio);
+        }
+        self.chain_epoch.set(stats.epoch as i64);
+        if let Some(height) = client.last_height() {
+            self.chain_height.set(height.0 as i64);
+        }
+
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .unwrap_or_else(|err| format!("# Failed to encode metrics: {}\n", err))
+    }
+}
+
+/// Replace the parameters of a request path by `{param}`
+pub fn route_template(path: &str) -> String {
+    let segments: Vec<&str> = path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| if ROUTE_SEGMENTS.contains(&segment) { segment } else { "{param}" })
+        .collect();
+    format!("/{}", segments.join("/"))
+}
@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, HashMap};
+use serde::Serialize;
+use namada_core::address::Address;
+use namada_core::hash::Hash;
+use namada_core::tendermint::block::{Block, CommitSig, Id as BlockId};
+use namada_core::tendermint::validator;
+use namada_sdk::events::extend::{Height, Info, Log};
+use namada_sdk::gas::event::GasUsed;
+use namada_sdk::rpc::TxAppliedEvents;
+use namada_sdk::tx::data::{compute_inner_tx_hash, TxType};
+use namada_sdk::tx::either::Either;
+use namada_sdk::tx::event::{Batch, Code};
+use namada_sdk::tx::{Section, Tx};
+use crate::models::error::ApiError;
+
+/// Hash of a transaction of a block: the hash of its header, as Namada
+/// identifies transactions, or of its bytes if it can't be decoded
+pub fn tx_hash(bytes: &[u8]) -> Hash {
+    Tx::try_from(bytes)
+        .map(|tx| tx.header_hash())
+        .unwrap_or_else(|_| Hash::sha256(bytes))
+}
+
+/// Name of a transaction type
+fn tx_type_name(tx_type: &TxType) -> &'static str {
+    match tx_type {
+        TxType::Raw => "raw",
+        TxType::Wrapper(_) => "wrapper",
+        TxType::Protocol(_) => "protocol",
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockResponse {
+    pub height: u64,
+    pub hash: String,
+    pub time: String,
+    pub chain_id: String,
+    pub last_block_hash: Option<String>,
+    pub app_hash: String,
+    pub data_hash: Option<String>,
+    pub validators_hash: String,
+    /// CometBFT address of the proposer
+    pub proposer_address: String,
+    /// Namada address of the proposer, null if it isn't a known validator
+    pub proposer: Option<String>,
+    pub tx_count: usize,
+    pub tx_hashes: Vec<String>,
+}
+
+impl BlockResponse {
+    pub fn new(block_id: &BlockId, block: &Block, proposer: Option<Address>) -> Self {
+        let header = &block.header;
+        Self {
+            height: header.height.value(),
+            hash: block_id.hash.to_string(),
+            time: header.time.to_rfc3339(),
+            chain_id: header.chain_id.to_string(),
+            last_block_hash: header.last_block_id.map(|id| id.hash.to_string()),
+            app_hash: header.app_hash.to_string(),
+            data_hash: header.data_hash.map(|hash| hash.to_string()),
+            validators_hash: header.validators_hash.to_string(),
+            proposer_address: header.proposer_address.to_string(),
+            proposer: proposer.map(|address| address.to_string()),
+            tx_count: block.data.len(),
+            tx_hashes: block.data.iter().map(|tx| tx_hash(tx).to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TxResponse {
+    pub hash: String,
+    pub height: u64,
+    /// Position of the transaction in its block
+    pub index: usize,
+    /// `wrapper`, `protocol` or `raw`
+    #[serde(rename = "type")]
+    pub tx_type: &'static str,
+    /// 0 on success, see `ResultCode` in the Namada SDK otherwise
+    pub code: u32,
+    pub gas_used: u64,
+    pub info: String,
+    pub log: String,
+    pub inner_txs: Vec<InnerTxResponse>,
+    /// Events emitted by the transaction, such as transfers
+    pub events: Vec<EventResponse>,
+}
+
+/// A transaction of a batch
+#[derive(Debug, Serialize)]
+pub struct InnerTxResponse {
+    pub hash: String,
+    /// Name of the transaction code, such as `tx_transfer`, null if the code has no tag
+    #[serde(rename = "type")]
+    pub tx_type: Option<String>,
+    /// `accepted`, `rejected` by a validity predicate or `failed`, null if it wasn't run
+    pub status: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct EventResponse {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub attributes: BTreeMap<String, String>,
+}
+
+impl TxResponse {
+    pub fn new(tx: &Tx, index: usize, events: TxAppliedEvents) -> Result<Self, ApiError> {
+        let TxAppliedEvents { applied, other } = events;
+        let malformed = |e: namada_sdk::events::EventError| {
+            ApiError::QueryError(format!("Malformed event of transaction {}: {}", tx.header_hash(), e))
+        };
+        let height = applied.read_attribute::<Height>().map_err(malformed)?;
+        let code = applied.read_attribute::<Code>().map_err(malformed)?;
+        let gas_used = applied.read_attribute::<GasUsed>().map_err(malformed)?;
+        // Only transactions that were run have a batch result
+        let batch = applied.read_attribute::<Batch<'_>>().ok();
+
+        let wrapper_hash = tx.wrapper_hash();
+        let inner_txs = tx.commitments().iter()
+            .map(|commitments| {
+                let tx_type = tx.get_section(&commitments.code_hash).and_then(|section| match section.as_ref() {
+                    Section::Code(code) => code.tag.as_deref().map(|tag| tag.trim_end_matches(".wasm").to_string()),
+                    _ => None,
+                });
+                let status = batch.as_ref()
+                    .and_then(|batch| batch.get_inner_tx_result(wrapper_hash.as_ref(), Either::Right(commitments)))
+                    .map(|result| match result {
+                        Ok(result) if result.is_accepted() => "accepted",
+                        Ok(_) => "rejected",
+                        Err(_) => "failed",
+                    });
+                InnerTxResponse {
+                    hash: compute_inner_tx_hash(wrapper_hash.as_ref(), Either::Right(commitments)).to_string(),
+                    tx_type,
+                    status,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            hash: tx.header_hash().to_string(),
+            height: height.0,
+            index,
+            tx_type: tx_type_name(&tx.header.tx_type),
+            code: code.to_u32(),
+            gas_used: gas_used.into(),
+            info: applied.read_attribute::<Info>().unwrap_or_default(),
+            log: applied.read_attribute::<Log>().unwrap_or_default(),
+            inner_txs,
+            events: other.into_iter()
+                .map(|event| EventResponse {
+                    event_type: event.kind().to_string(),
+                    attributes: event.into_attributes(),
+                })
+                .collect(),
+        })
+    }
+}
+
+/// Vote of a validator on a block
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureStatus {
+    /// Voted for the block
+    Signed,
+    /// Did not vote, or its vote arrived too late
+    Absent,
+    /// Voted for no block
+    Nil,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BlockSignaturesResponse {
+    pub height: u64,
+    /// Whether the commit is final. The commit of the last block only holds
+    /// the signatures seen so far.
+    pub canonical: bool,
+    pub total_voting_power: u64,
+    pub signed_voting_power: u64,
+    pub signatures: Vec<ValidatorSignature>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidatorSignature {
+    /// Namada address of the validator, null if it isn't a known validator
+    pub validator: Option<String>,
+    /// CometBFT address of the validator
+    pub consensus_address: String,
+    pub status: SignatureStatus,
+    pub voting_power: u64,
+}
+
+impl BlockSignaturesResponse {
+    /// Status of each validator of the set of the block, by the signatures of
+    /// its commit. `addresses` maps CometBFT addresses to Namada addresses.
+    pub fn new(
+        height: u64,
+        canonical: bool,
+        validators: &[validator::Info],
+        signatures: &[CommitSig],
+        addresses: &HashMap<String, Address>,
+    ) -> Self {
+        let statuses: HashMap<_, _> = signatures.iter()
+            .filter_map(|signature| match signature {
+                CommitSig::BlockIdFlagCommit { validator_address, .. } => Some((*validator_address, SignatureStatus::Signed)),
+                CommitSig::BlockIdFlagNil { validator_address, .. } => Some((*validator_address, SignatureStatus::Nil)),
+                CommitSig::BlockIdFlagAbsent => None,
+            })
+            .collect();
+
+        let signatures: Vec<_> = validators.iter()
+            .map(|info| {
+                let consensus_address = info.address.to_string();
+                ValidatorSignature {
+                    validator: addresses.get(&consensus_address).map(|address| address.to_string()),
+                    status: statuses.get(&info.address).copied().unwrap_or(SignatureStatus::Absent),
+                    voting_power: info.power.value(),
+                    consensus_address,
+                }
+            })
+            .collect();
+
+        Self {
+            height,
+            canonical,
+            total_voting_power: signatures.iter().map(|signature| signature.voting_power).sum(),
+            signed_voting_power: signatures.iter()
+                .filter(|signature| signature.status == SignatureStatus::Signed)
+                .map(|signature| signature.voting_power)
+                .sum(),
+            signatures,
+        }
+    }
+}
@@ -1,6 +1,7 @@
-use warp::{Rejection, Reply, http::StatusCode};
+use warp::{Rejection, Reply, http::StatusCode, http::header::{HeaderValue, RETRY_AFTER}};
 use serde::Serialize;
 use std::convert::Infallible;
+use crate::client::ClientError;
 
 #[derive(Debug, thiserror::Error)]
 pub enum ApiError {
@@ -14,16 +15,43 @@ pub enum ApiError {
     InvalidAddress(String),
     #[error("Invalid pagination parameters: {0}")]
     InvalidPagination(String),
+    #[error("Invalid epoch: {0}")]
+    InvalidEpoch(String),
+    #[error("Invalid block height: {0}")]
+    InvalidHeight(String),
     #[error("Invalid Tendermint address: {0}")]
     InvalidTendermintAddress(String),
+    #[error("Request timed out: {0}")]
+    Timeout(String),
     #[error("RPC connection error: {0}")]
     RpcConnectionError(String),
     #[error("Internal server error: {0}")]
     InternalError(String),
+    #[error("Invalid API key")]
+    InvalidApiKey,
+    #[error("Rate limit exceeded, retry in {retry_after_secs} s")]
+    RateLimited { retry_after_secs: u64 },
 }
 
 impl warp::reject::Reject for ApiError {}
 
+/// Nodes that can't be reached are reported with `503`, failed queries with `500`
+impl From<ClientError> for ApiError {
+    fn from(err: ClientError) -> Self {
+        if err.is_connection_error() {
+            ApiError::RpcConnectionError(err.to_string())
+        } else {
+            ApiError::QueryError(err.to_string())
+        }
+    }
+}
+
+impl From<ClientError> for Rejection {
+    fn from(err: ClientError) -> Self {
+        warp::reject::custom(ApiError::from(err))
+    }
+}
+
 #[derive(Serialize)]
 struct ErrorResponse {
     error: String,
@@ -32,6 +60,7 @@ struct ErrorResponse {
 }
 
 pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible> {
+    let mut retry_after = None;
     let (code, message, details) = if err.is_not_found() {
         (StatusCode::NOT_FOUND, "Not Found".to_string(), None)
     } else if let Some(e) = err.find::<ApiError>() {
@@ -41,10 +70,24 @@ pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible>
             ApiError::QueryError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, "Query error occurred".to_string(), Some(msg.clone())),
             ApiError::InvalidAddress(msg) => (StatusCode::BAD_REQUEST, "Invalid address format".to_string(), Some(msg.clone())),
             ApiError::InvalidPagination(msg) => (StatusCode::BAD_REQUEST, "Invalid pagination parameters".to_string(), Some(msg.clone())),
+            ApiError::InvalidEpoch(msg) => (StatusCode::BAD_REQUEST, "Invalid epoch".to_string(), Some(msg.clone())),
+            ApiError::InvalidHeight(msg) => (StatusCode::BAD_REQUEST, "Invalid block height".to_string(), Some(msg.clone())),
             ApiError::InvalidTendermintAddress(msg) => (StatusCode::BAD_REQUEST, "Invalid Tendermint address".to_string(), Some(msg.clone())),
+            ApiError::Timeout(msg) => (StatusCode::GATEWAY_TIMEOUT, "Request timed out".to_string(), Some(msg.clone())),
             ApiError::RpcConnectionError(msg) => (StatusCode::SERVICE_UNAVAILABLE, "RPC connection error".to_string(), Some(msg.clone())),
             ApiError::InternalError(msg) => (StatusCode::INTERNAL_SERVER_ERROR, "Internal server error".to_string(), Some(msg.clone())),
+            ApiError::InvalidApiKey => (StatusCode::UNAUTHORIZED, "Invalid API key".to_string(), None),
+            ApiError::RateLimited { retry_after_secs } => {
+                retry_after = Some(*retry_after_secs);
+                (StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded".to_string(), Some(e.to_string()))
+            }
         }
+    } else if let Some(e) = err.find::<warp::cors::CorsForbidden>() {
+        (StatusCode::FORBIDDEN, "CORS request forbidden".to_string(), Some(e.to_string()))
+    } else if let Some(e) = err.find::<warp::reject::MissingHeader>() {
+        (StatusCode::BAD_REQUEST, "Missing header".to_string(), Some(e.to_string()))
+    } else if let Some(e) = err.find::<warp::reject::InvalidHeader>() {
+        (StatusCode::BAD_REQUEST, "Invalid header".to_string(), Some(e.to_string()))
     } else {
         (StatusCode::INTERNAL_SERVER_ERROR, "Internal Server Error".to_string(), None)
     };
@@ -54,5 +97,9 @@ pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible>
         details,
     });
 
-    Ok(warp::reply::with_status(json, code))
+    let mut response = warp::reply::with_status(json, code).into_response();
+    if let Some(retry_after) = retry_after {
+        response.headers_mut().insert(RETRY_AFTER, HeaderValue::from(retry_after));
+    }
+    Ok(response)
 } 
\ No newline at end of file
@@ -0,0 +1,109 @@
+use serde::{Deserialize, Serialize};
+use crate::models::block::BlockResponse;
+use crate::models::error::ApiError;
+use crate::models::pos::{ValidatorDetailsResponse, WeightedValidatorResponse};
+
+/// Topic of the chain events a client can subscribe to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Topic {
+    NewBlock,
+    NewEpoch,
+    ValidatorSetChange,
+    LivenessUpdate,
+}
+
+impl std::str::FromStr for Topic {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "new_block" => Ok(Self::NewBlock),
+            "new_epoch" => Ok(Self::NewEpoch),
+            "validator_set_change" => Ok(Self::ValidatorSetChange),
+            "liveness_update" => Ok(Self::LivenessUpdate),
+            _ => Err(ApiError::BadRequest(format!(
+                "Invalid topic '{}', expected new_block, new_epoch, validator_set_change or liveness_update", s
+            ))),
+        }
+    }
+}
+
+/// Event of the chain, sent to the clients subscribed to its topic
+#[derive(Debug, Serialize)]
+#[serde(tag = "topic", content = "data", rename_all = "snake_case")]
+pub enum ChainEvent {
+    NewBlock(BlockResponse),
+    NewEpoch(NewEpochEvent),
+    ValidatorSetChange(ValidatorSetChangeEvent),
+    LivenessUpdate(LivenessUpdateEvent),
+}
+
+impl ChainEvent {
+    pub fn topic(&self) -> Topic {
+        match self {
+            ChainEvent::NewBlock(_) => Topic::NewBlock,
+            ChainEvent::NewEpoch(_) => Topic::NewEpoch,
+            ChainEvent::ValidatorSetChange(_) => Topic::ValidatorSetChange,
+            ChainEvent::LivenessUpdate(_) => Topic::LivenessUpdate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NewEpochEvent {
+    pub epoch: u64,
+    /// Block the epoch was seen at, the first one of a new epoch
+    pub height: u64,
+}
+
+/// State of a watched validator at a new epoch, sent to the streams watching it
+pub struct ValidatorSnapshot {
+    /// Block the epoch was seen at
+    pub height: u64,
+    pub details: ValidatorDetailsResponse,
+}
+
+/// Changes of the consensus validator set of an epoch from the previous one
+#[derive(Debug, Serialize)]
+pub struct ValidatorSetChangeEvent {
+    pub epoch: u64,
+    pub added: Vec<WeightedValidatorResponse>,
+    pub removed: Vec<String>,
+    /// Validators that stayed in the set with another stake
+    pub updated: Vec<WeightedValidatorResponse>,
+}
+
+/// Consensus validators whose count of missed votes changed with a block
+#[derive(Debug, Serialize)]
+pub struct LivenessUpdateEvent {
+    pub height: u64,
+    pub liveness_window_len: u64,
+    pub liveness_threshold: String,
+    pub validators: Vec<ValidatorLivenessUpdate>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ValidatorLivenessUpdate {
+    pub address: String,
+    pub consensus_address: String,
+    pub missed_votes: u64,
+}
+
+/// Message sent by a WebSocket client
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "lowercase")]
+pub enum WsRequest {
+    Subscribe { topics: Vec<Topic> },
+    Unsubscribe { topics: Vec<Topic> },
+}
+
+/// Message sent to a WebSocket client
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum WsMessage<'a> {
+    /// Topics the client is subscribed to, after each request
+    Subscribed { topics: Vec<Topic> },
+    Event(&'a ChainEvent),
+    Error { message: String },
+}
@@ -0,0 +1,300 @@
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use namada_core::address::Address;
+use namada_core::chain::Epoch;
+use namada_sdk::governance::storage::proposal::{ProposalType, StorageProposal};
+use namada_sdk::governance::storage::vote::ProposalVote;
+use namada_sdk::governance::utils::{ProposalResult, ProposalStatus, TallyResult, TallyType, Vote};
+use crate::models::error::ApiError;
+
+/// Status of a proposal, from its voting period and the current epoch
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProposalStatusResponse {
+    Pending,
+    Voting,
+    Ended,
+}
+
+impl ProposalStatusResponse {
+    pub fn of(proposal: &StorageProposal, epoch: Epoch) -> Self {
+        match proposal.get_status(epoch) {
+            ProposalStatus::Pending => Self::Pending,
+            ProposalStatus::OnGoing => Self::Voting,
+            ProposalStatus::Ended => Self::Ended,
+        }
+    }
+}
+
+impl std::str::FromStr for ProposalStatusResponse {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(Self::Pending),
+            "voting" => Ok(Self::Voting),
+            "ended" => Ok(Self::Ended),
+            _ => Err(ApiError::BadRequest(format!(
+                "Invalid proposal status '{}', expected pending, voting or ended", s
+            ))),
+        }
+    }
+}
+
+/// Name of a proposal type
+fn proposal_type_name(proposal_type: &ProposalType) -> &'static str {
+    match proposal_type {
+        ProposalType::Default => "default",
+        ProposalType::DefaultWithWasm(_) => "default_with_wasm",
+        ProposalType::PGFSteward(_) => "pgf_steward",
+        ProposalType::PGFPayment(_) => "pgf_funding",
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ProposalsQuery {
+    pub page: Option<u32>,
+    pub per_page: Option<u32>,
+    pub status: Option<String>,
+}
+
+impl ProposalsQuery {
+    /// Validate the query parameters, returning the status filter
+    pub fn validate(&self) -> Result<Option<ProposalStatusResponse>, ApiError> {
+        if self.page == Some(0) {
+            return Err(ApiError::InvalidPagination("Page number must be greater than 0".to_string()));
+        }
+
+        if let Some(per_page) = self.per_page {
+            if per_page == 0 {
+                return Err(ApiError::InvalidPagination("Items per page must be greater than 0".to_string()));
+            }
+            if per_page > 50 {
+                return Err(ApiError::InvalidPagination("Maximum items per page is 50".to_string()));
+            }
+        }
+
+        self.status.as_deref().map(str::parse).transpose()
+    }
+}
+
+/// A proposal in the list of proposals
+#[derive(Debug, Serialize)]
+pub struct ProposalSummaryResponse {
+    pub id: u64,
+    pub title: Option<String>,
+    #[serde(rename = "type")]
+    pub proposal_type: &'static str,
+    pub author: String,
+    pub voting_start_epoch: u64,
+    pub voting_end_epoch: u64,
+    pub activation_epoch: u64,
+    pub status: ProposalStatusResponse,
+}
+
+impl ProposalSummaryResponse {
+    pub fn new(proposal: &StorageProposal, epoch: Epoch) -> Self {
+        Self {
+            id: proposal.id,
+            title: proposal.content.get("title").cloned(),
+            proposal_type: proposal_type_name(&proposal.r#type),
+            author: proposal.author.to_string(),
+            voting_start_epoch: proposal.voting_start_epoch.0,
+            voting_end_epoch: proposal.voting_end_epoch.0,
+            activation_epoch: proposal.activation_epoch.0,
+            status: ProposalStatusResponse::of(proposal, epoch),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProposalsResponse {
+    pub epoch: u64,
+    pub proposals: Vec<ProposalSummaryResponse>,
+    pub pagination: PaginationResponse,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PaginationResponse {
+    pub total: usize,
+    pub page: u32,
+    pub per_page: u32,
+    pub total_pages: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProposalResponse {
+    pub epoch: u64,
+    pub id: u64,
+    pub content: BTreeMap<String, String>,
+    #[serde(rename = "type")]
+    pub proposal_type: &'static str,
+    /// Hash of the wasm code, stewards added or removed, or fundings, by proposal type
+    pub data: Option<serde_json::Value>,
+    pub author: String,
+    pub voting_start_epoch: u64,
+    pub voting_end_epoch: u64,
+    pub activation_epoch: u64,
+    pub status: ProposalStatusResponse,
+    /// Votes counted so far while voting, final result once ended, null while pending
+    pub tally: Option<TallyResponse>,
+}
+
+impl ProposalResponse {
+    pub fn new(epoch: Epoch, proposal: StorageProposal, tally: Option<ProposalResult>) -> Self {
+        let data = match &proposal.r#type {
+            ProposalType::Default => None,
+            ProposalType::DefaultWithWasm(hash) => Some(serde_json::Value::String(hash.to_string())),
+            ProposalType::PGFSteward(stewards) => serde_json::to_value(stewards).ok(),
+            ProposalType::PGFPayment(fundings) => serde_json::to_value(fundings).ok(),
+        };
+        Self {
+            epoch: epoch.0,
+            status: ProposalStatusResponse::of(&proposal, epoch),
+            proposal_type: proposal_type_name(&proposal.r#type),
+            data,
+            id: proposal.id,
+            author: proposal.author.to_string(),
+            voting_start_epoch: proposal.voting_start_epoch.0,
+            voting_end_epoch: proposal.voting_end_epoch.0,
+            activation_epoch: proposal.activation_epoch.0,
+            content: proposal.content,
+            tally: tally.map(TallyResponse::from),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct TallyResponse {
+    /// `passed` or `rejected`. While voting, the result if voting ended now.
+    pub result: &'static str,
+    pub tally_type: &'static str,
+    pub total_voting_power: String,
+    pub total_yay_power: String,
+    pub total_nay_power: String,
+    pub total_abstain_power: String,
+}
+
+impl From<ProposalResult> for TallyResponse {
+    fn from(result: ProposalResult) -> Self {
+        Self {
+            result: match result.result {
+                TallyResult::Passed => "passed",
+                TallyResult::Rejected => "rejected",
+            },
+            tally_type: match result.tally_type {
+                TallyType::TwoFifths => "two_fifths",
+                TallyType::OneHalfOverOneThird => "one_half_over_one_third",
+                TallyType::LessOneHalfOverOneThirdNay => "less_one_half_over_one_third_nay",
+            },
+            total_voting_power: result.total_voting_power.to_string(),
+            total_yay_power: result.total_yay_power.to_string(),
+            total_nay_power: result.total_nay_power.to_string(),
+            total_abstain_power: result.total_abstain_power.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct GovParametersResponse {
+    pub min_proposal_fund: String,
+    pub max_proposal_code_size: u64,
+    pub min_proposal_voting_period: u64,
+    pub max_proposal_period: u64,
+    pub max_proposal_content_size: u64,
+    pub min_proposal_grace_epochs: u64,
+    pub max_proposal_latency: u64,
+}
+
+/// Name of a vote
+fn vote_name(vote: &ProposalVote) -> &'static str {
+    match vote {
+        ProposalVote::Yay => "yay",
+        ProposalVote::Nay => "nay",
+        ProposalVote::Abstain => "abstain",
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ProposalVotesResponse {
+    pub epoch: u64,
+    pub proposal_id: u64,
+    pub votes: Vec<VoteResponse>,
+}
+
+/// A vote cast on a proposal. Delegators vote once per validator they delegate to.
+#[derive(Debug, Serialize)]
+pub struct VoteResponse {
+    pub voter: String,
+    pub validator: String,
+    pub vote: &'static str,
+    /// `validator` or `delegator`
+    pub voter_type: &'static str,
+}
+
+impl From<&Vote> for VoteResponse {
+    fn from(vote: &Vote) -> Self {
+        Self {
+            voter: vote.delegator.to_string(),
+            validator: vote.validator.to_string(),
+            vote: vote_name(&vote.data),
+            voter_type: if vote.is_validator() { "validator" } else { "delegator" },
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct EffectiveVoteResponse {
+    pub epoch: u64,
+    pub proposal_id: u64,
+    pub address: String,
+    /// Whether the address voted itself through at least one of its validators
+    pub voted: bool,
+    pub votes: Vec<EffectiveVote>,
+}
+
+/// How the stake of an address at a validator votes
+#[derive(Debug, Serialize)]
+pub struct EffectiveVote {
+    pub validator: String,
+    /// Null when neither the address nor the validator voted
+    pub vote: Option<&'static str>,
+    /// Whether the vote is the one of the validator
+    pub inherited: bool,
+}
+
+impl EffectiveVote {
+    /// Effective votes of `address`, one for each validator it delegates to:
+    /// its own vote through the validator if it voted one, otherwise the vote
+    /// of the validator. Its own votes through validators it no longer
+    /// delegates to are kept as well.
+    pub fn of(address: &Address, votes: &[Vote], validators: &[Address]) -> (bool, Vec<Self>) {
+        let own_vote = |validator: &Address| votes.iter()
+            .find(|vote| &vote.delegator == address && &vote.validator == validator);
+        let mut effective_votes: Vec<_> = validators.iter()
+            .map(|validator| match own_vote(validator) {
+                Some(vote) => Self {
+                    validator: validator.to_string(),
+                    vote: Some(vote_name(&vote.data)),
+                    inherited: false,
+                },
+                None => Self {
+                    validator: validator.to_string(),
+                    vote: votes.iter()
+                        .find(|vote| vote.is_validator() && &vote.validator == validator)
+                        .map(|vote| vote_name(&vote.data)),
+                    inherited: true,
+                },
+            })
+            .collect();
+        effective_votes.extend(votes.iter()
+            .filter(|vote| &vote.delegator == address && !validators.contains(&vote.validator))
+            .map(|vote| Self {
+                validator: vote.validator.to_string(),
+                vote: Some(vote_name(&vote.data)),
+                inherited: false,
+            }));
+        let voted = effective_votes.iter().any(|vote| !vote.inherited);
+        (voted, effective_votes)
+    }
+}
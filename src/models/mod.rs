@@ -1,3 +1,6 @@
 pub mod pos;
 pub mod error;
-pub mod token; 
\ No newline at end of file
+pub mod token;
+pub mod gov;
+pub mod block;
+pub mod events;
\ No newline at end of file
@@ -1,4 +1,5 @@
 use serde::Serialize;
+use namada_proof_of_stake::types::SlashType;
 
 #[derive(Serialize)]
 pub struct LivenessInfoResponse {
@@ -19,9 +20,10 @@ pub struct ValidatorResponse {
     pub address: String,
 }
 
-#[derive(Serialize)]
+#[derive(Clone, Serialize)]
 pub struct ValidatorDetailsResponse {
     pub address: String,
+    pub epoch: u64,
     pub state: String,
     pub stake: String,
     pub commission_rate: String,
@@ -29,7 +31,17 @@ pub struct ValidatorDetailsResponse {
     pub metadata: Option<ValidatorMetadata>,
 }
 
-#[derive(Serialize)]
+impl ValidatorDetailsResponse {
+    /// Whether the state, stake or commission of the validator differ
+    pub fn has_changed(&self, other: &Self) -> bool {
+        self.state != other.state
+            || self.stake != other.stake
+            || self.commission_rate != other.commission_rate
+            || self.max_commission_change_per_epoch != other.max_commission_change_per_epoch
+    }
+}
+
+#[derive(Clone, Serialize)]
 pub struct ValidatorMetadata {
     pub email: String,
     pub description: Option<String>,
@@ -41,6 +53,7 @@ pub struct ValidatorMetadata {
 
 #[derive(Serialize)]
 pub struct DelegationsResponse {
+    pub epoch: u64,
     pub delegations: Vec<Delegation>,
 }
 
@@ -50,8 +63,159 @@ pub struct Delegation {
     pub amount: String,
 }
 
+#[derive(Serialize)]
+pub struct BondsResponse {
+    pub source: String,
+    pub validator: String,
+    pub epoch: u64,
+    pub bonds: Vec<Bond>,
+}
+
+#[derive(Serialize)]
+pub struct Bond {
+    pub start_epoch: u64,
+    pub amount: String,
+    pub slashed_amount: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct UnbondsResponse {
+    pub source: String,
+    pub validator: String,
+    pub epoch: u64,
+    pub unbonds: Vec<Unbond>,
+}
+
+#[derive(Serialize)]
+pub struct Unbond {
+    pub start_epoch: u64,
+    pub withdraw_epoch: u64,
+    pub amount: String,
+    pub slashed_amount: Option<String>,
+}
+
+#[derive(Serialize)]
+pub struct WithdrawableResponse {
+    pub source: String,
+    pub validator: String,
+    pub epoch: u64,
+    pub withdrawable: String,
+    pub pending: Vec<PendingWithdrawal>,
+}
+
+#[derive(Serialize)]
+pub struct SourceWithdrawableResponse {
+    pub source: String,
+    pub epoch: u64,
+    pub withdrawable: String,
+    pub pending: Vec<PendingWithdrawal>,
+    pub validators: Vec<ValidatorWithdrawable>,
+}
+
+#[derive(Serialize)]
+pub struct ValidatorWithdrawable {
+    pub validator: String,
+    pub withdrawable: String,
+    pub pending: Vec<PendingWithdrawal>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingWithdrawal {
+    pub withdraw_epoch: u64,
+    pub amount: String,
+}
+
+#[derive(Serialize)]
+pub struct RewardsResponse {
+    pub validator: String,
+    pub delegator: Option<String>,
+    pub epoch: u64,
+    pub rewards: String,
+}
+
+#[derive(Serialize)]
+pub struct TotalRewardsResponse {
+    pub delegator: String,
+    pub epoch: u64,
+    pub total: String,
+    pub validators: Vec<ValidatorRewards>,
+}
+
+#[derive(Serialize)]
+pub struct ValidatorRewards {
+    pub validator: String,
+    pub rewards: String,
+}
+
+#[derive(Serialize)]
+pub struct ValidatorSlashesResponse {
+    pub address: String,
+    pub epoch: u64,
+    pub last_infraction_epoch: Option<u64>,
+    pub slashes: Vec<SlashResponse>,
+}
+
+#[derive(Serialize)]
+pub struct SlashesResponse {
+    pub epoch: u64,
+    pub slashes: Vec<SlashResponse>,
+}
+
+#[derive(Serialize)]
+pub struct SlashResponse {
+    pub validator: String,
+    pub epoch: u64,
+    pub block_height: u64,
+    /// `duplicate_vote` or `light_client_attack`
+    pub slash_type: &'static str,
+    pub rate: String,
+}
+
+/// Name of a slash type
+pub fn slash_type_name(slash_type: &SlashType) -> &'static str {
+    match slash_type {
+        SlashType::DuplicateVote => "duplicate_vote",
+        SlashType::LightClientAttack => "light_client_attack",
+    }
+}
+
+#[derive(Serialize)]
+pub struct TotalStakeResponse {
+    pub epoch: u64,
+    pub total_stake: String,
+    pub native_token: String,
+    pub total_supply: String,
+    pub staking_ratio: String,
+}
+
+#[derive(Serialize)]
+pub struct PosParametersResponse {
+    pub max_validator_slots: u64,
+    pub pipeline_len: u64,
+    pub unbonding_len: u64,
+    pub tm_votes_per_token: String,
+    pub block_proposer_reward: String,
+    pub block_vote_reward: String,
+    pub max_inflation_rate: String,
+    pub target_staked_ratio: String,
+    pub duplicate_vote_min_slash_rate: String,
+    pub light_client_attack_min_slash_rate: String,
+    pub cubic_slashing_window_length: u64,
+    pub withdrawable_epoch_offset: u64,
+    pub validator_stake_threshold: String,
+    /// Namada has no separate self-bond minimum: a validator is active once
+    /// its bonded stake, self-bonded or delegated, reaches the threshold
+    pub min_self_bond: String,
+    pub liveness_window_check: u64,
+    pub liveness_threshold: String,
+    pub rewards_gain_p: String,
+    pub rewards_gain_d: String,
+    pub max_proposal_period: u64,
+}
+
 #[derive(Debug, Serialize)]
 pub struct ValidatorSetResponse {
+    pub epoch: u64,
     pub validators: Vec<WeightedValidatorResponse>,
 }
 
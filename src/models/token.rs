@@ -9,8 +9,8 @@ pub struct TokenBalanceResponse {
     pub owner: String, 
     /// Token balance amount
     pub balance: String,
-    /// Block height at which the balance was queried (optional)
-    pub height: Option<u64>,
+    /// Block height at which the balance was queried
+    pub height: u64,
 }
 
 /// Request parameters for token balance query
@@ -53,6 +53,8 @@ pub struct TokenTotalSupplyResponse {
     pub token: String,
     /// Total supply amount
     pub total_supply: String,
+    /// Block height at which the total supply was queried
+    pub height: u64,
 }
 
 /// Response for native token query
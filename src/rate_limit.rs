@@ -0,0 +1,272 @@
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv6Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use ipnet::IpNet;
+use warp::http::header::FORWARDED;
+use warp::http::HeaderMap;
+use warp::path::FullPath;
+use warp::{Filter, Rejection};
+use crate::config::{Config, RateLimitsConfig, RateQuota};
+use crate::metrics::route_template;
+use crate::models::error::ApiError;
+
+/// Header carrying the API key of a request
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Header carrying the addresses a request was forwarded for, the client first
+const X_FORWARDED_FOR_HEADER: &str = "x-forwarded-for";
+
+/// Number of buckets above which the full ones are dropped, as they are
+/// equivalent to no bucket at all
+const MAX_BUCKETS: usize = 100_000;
+
+/// Routes querying many validators, delegations, proposals or votes at once,
+/// or a validator at two heights
+const HEAVY_ROUTES: &[&str] = &[
+    "/api/pos/validators",
+    "/api/pos/validators_details",
+    "/api/pos/validator_set/consensus",
+    "/api/pos/validator_set/below_capacity",
+    "/api/pos/slashes",
+    "/api/pos/liveness_info",
+    "/api/pos/withdrawable/{param}",
+    "/api/pos/total_rewards/{param}",
+    "/api/gov/proposals",
+    "/api/gov/proposals/{param}",
+    "/api/gov/proposals/{param}/votes",
+    "/api/gov/proposals/{param}/votes/{param}",
+    "/api/blocks/{param}/signatures",
+    "/api/stream/validators/{param}",
+];
+
+/// Class of a route, each class having its own quotas
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    Standard,
+    Heavy,
+    Health,
+}
+
+impl RouteClass {
+    /// Class of the route of a request path
+    pub fn of(path: &str) -> Self {
+        let route = route_template(path);
+        if route == "/metrics" || route.starts_with("/api/health/") || route.starts_with("/api/docs") {
+            RouteClass::Health
+        } else if HEAVY_ROUTES.contains(&route.as_str()) {
+            RouteClass::Heavy
+        } else {
+            RouteClass::Standard
+        }
+    }
+}
+
+impl RateLimitsConfig {
+    fn quota(&self, class: RouteClass) -> Option<RateQuota> {
+        match class {
+            RouteClass::Standard => self.standard,
+            RouteClass::Heavy => self.heavy,
+            RouteClass::Health => self.health,
+        }
+    }
+}
+
+/// Who a bucket belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Client {
+    Ip(IpAddr),
+    /// Index of the API key in the configuration
+    ApiKey(usize),
+}
+
+struct Bucket {
+    quota: RateQuota,
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl Bucket {
+    fn new(quota: RateQuota, now: Instant) -> Self {
+        Self { quota, tokens: f64::from(quota.burst), updated_at: now }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.quota.requests_per_second).min(f64::from(self.quota.burst));
+        self.updated_at = now;
+    }
+
+    /// Take a token, or return the time until one is available
+    fn take(&mut self, now: Instant) -> Result<(), Duration> {
+        self.refill(now);
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - self.tokens) / self.quota.requests_per_second))
+        }
+    }
+
+    fn is_full(&mut self, now: Instant) -> bool {
+        self.refill(now);
+        self.tokens >= f64::from(self.quota.burst)
+    }
+}
+
+/// Token bucket rate limiter, with a bucket per route class and client.
+/// A client is identified by its API key when it sends one, by its IP
+/// address otherwise.
+pub struct RateLimiter {
+    enabled: bool,
+    trusted_proxies: Vec<IpNet>,
+    quotas: RateLimitsConfig,
+    api_keys: Vec<RateLimitsConfig>,
+    /// Index of each API key in `api_keys`
+    key_indices: HashMap<String, usize>,
+    buckets: Mutex<HashMap<(Client, RouteClass), Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            enabled: config.rate_limit_enabled,
+            trusted_proxies: config.trusted_proxies.clone(),
+            quotas: config.rate_limits.clone(),
+            api_keys: config.api_keys.iter().map(|api_key| api_key.rate_limits.clone()).collect(),
+            key_indices: config.api_keys.iter()
+                .enumerate()
+                .map(|(index, api_key)| (api_key.key.clone(), index))
+                .collect(),
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn is_trusted(&self, ip: IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|proxy| proxy.contains(&ip))
+    }
+
+    /// Address of the client of a request coming from `peer`. The addresses
+    /// forwarded by trusted proxies are followed from the last one, up to the
+    /// first address that isn't a trusted proxy.
+    pub fn client_ip(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        let mut ip = peer?;
+        if !self.is_trusted(ip) {
+            return Some(ip);
+        }
+
+        let mut forwarded = forwarded_for(headers).into_iter().rev();
+        while self.is_trusted(ip) {
+            // Unknown and obfuscated addresses leave the proxy as the client
+            match forwarded.next().and_then(|node| parse_node(&node)) {
+                Some(node) => ip = node,
+                None => break,
+            }
+        }
+        Some(ip)
+    }
+
+    /// Take a token from the bucket of the client for the route of `path`
+    pub fn check(&self, ip: Option<IpAddr>, api_key: Option<&str>, path: &str) -> Result<(), ApiError> {
+        if !self.enabled {
+            return Ok(());
+        }
+
+        let class = RouteClass::of(path);
+        let (client, quota) = match api_key {
+            Some(key) => {
+                let index = *self.key_indices.get(key).ok_or(ApiError::InvalidApiKey)?;
+                let quota = self.api_keys[index].quota(class).or(self.quotas.quota(class));
+                (Client::ApiKey(index), quota)
+            }
+            None => match ip {
+                Some(ip) => (Client::Ip(client_network(ip)), self.quotas.quota(class)),
+                // Such clients couldn't be told apart, so they must send an API key
+                None if self.quotas.quota(class).is_some() => {
+                    return Err(ApiError::BadRequest(
+                        "The address of the client is unknown, send an API key instead".to_string()
+                    ));
+                }
+                None => return Ok(()),
+            },
+        };
+        let Some(quota) = quota else {
+            return Ok(());
+        };
+
+        let now = Instant::now();
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= MAX_BUCKETS && !buckets.contains_key(&(client, class)) {
+            buckets.retain(|_, bucket| !bucket.is_full(now));
+        }
+        buckets.entry((client, class))
+            .or_insert_with(|| Bucket::new(quota, now))
+            .take(now)
+            .map_err(|wait| ApiError::RateLimited { retry_after_secs: wait.as_secs_f64().ceil().max(1.0) as u64 })
+    }
+}
+
+/// Address a client is limited by. IPv6 clients usually get a whole /64
+/// network, so they are limited by network.
+fn client_network(ip: IpAddr) -> IpAddr {
+    match ip {
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => IpAddr::V4(ip),
+            None => IpAddr::V6(Ipv6Addr::from(ip.to_bits() & !u128::from(u64::MAX))),
+        },
+        ip => ip,
+    }
+}
+
+/// Addresses a request was forwarded for, from the `X-Forwarded-For` header,
+/// or else from the `for` parameters of the `Forwarded` header (RFC 7239)
+fn forwarded_for(headers: &HeaderMap) -> Vec<String> {
+    let values = |name| headers.get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .map(str::trim);
+
+    let x_forwarded_for: Vec<String> = values(X_FORWARDED_FOR_HEADER).map(str::to_string).collect();
+    if !x_forwarded_for.is_empty() {
+        return x_forwarded_for;
+    }
+    values(FORWARDED.as_str())
+        .filter_map(|element| element.split(';').find_map(|pair| {
+            let (name, value) = pair.trim().split_once('=')?;
+            name.eq_ignore_ascii_case("for").then(|| value.trim_matches('"').to_string())
+        }))
+        .collect()
+}
+
+/// IP address of a forwarded node, with an optional port, IPv6 addresses
+/// being in brackets when they have one
+fn parse_node(node: &str) -> Option<IpAddr> {
+    if let Some(rest) = node.strip_prefix('[') {
+        return rest.split_once(']')?.0.parse().ok();
+    }
+    node.parse().ok()
+        .or_else(|| node.parse::<SocketAddr>().ok().map(|addr| addr.ip()))
+}
+
+/// Reject the requests of clients over their quota with `ApiError::RateLimited`,
+/// the requests with an unknown API key with `ApiError::InvalidApiKey`, and
+/// the requests of clients without an address or API key with `ApiError::BadRequest`
+pub fn rate_limit(limiter: Arc<RateLimiter>) -> impl Filter<Extract = (), Error = Rejection> + Clone {
+    warp::addr::remote()
+        .and(warp::header::headers_cloned())
+        .and(warp::path::full())
+        .and_then(move |addr: Option<SocketAddr>, headers: HeaderMap, path: FullPath| {
+            let limiter = limiter.clone();
+            async move {
+                let api_key = match headers.get(API_KEY_HEADER) {
+                    Some(key) => Some(key.to_str().map_err(|_| warp::reject::custom(ApiError::InvalidApiKey))?),
+                    None => None,
+                };
+                let ip = limiter.client_ip(addr.map(|addr| addr.ip()), &headers);
+                limiter.check(ip, api_key, path.as_str())
+                    .map_err(warp::reject::custom)
+            }
+        })
+        .untuple_one()
+}
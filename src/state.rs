@@ -1,5 +1,6 @@
 use std::sync::Arc;
 use crate::client::NamadaClient;
+use crate::config::RpcEndpointConfig;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -8,7 +9,7 @@ pub struct AppState {
 
 impl AppState {
     pub async fn new(rpc_url: String) -> Result<Self, crate::client::ClientError> {
-        let client = NamadaClient::new(rpc_url).await?;
+        let client = NamadaClient::with_endpoints(vec![RpcEndpointConfig { url: rpc_url, priority: 0, weight: 1 }], 0).await?;
         Ok(Self {
             namada_client: Arc::new(client),
         })
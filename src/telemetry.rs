@@ -0,0 +1,166 @@
+use std::convert::Infallible;
+use std::time::Instant;
+use thiserror::Error;
+use tracing::field::Empty;
+use tracing::{info_span, Span, Subscriber};
+use tracing_subscriber::fmt::format::FmtSpan;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::{SubscriberInitExt, TryInitError};
+use tracing_subscriber::{EnvFilter, Layer, Registry};
+use warp::http::header::{HeaderMap, HeaderValue};
+use warp::{Filter, Reply};
+use crate::config::{Config, LogFormat};
+use crate::metrics::route_template;
+
+/// Header carrying the ID of a request, from the client or generated by the API
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest request ID accepted from a client
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Log filter used when `RUST_LOG` is not set. The events of `warp::trace`
+/// repeat what the request spans already record.
+const DEFAULT_LOG_FILTER: &str = "info,warp::filters::trace=off";
+
+#[derive(Error, Debug)]
+pub enum TelemetryError {
+    #[error("Failed to install the logger: {0}")]
+    Init(#[from] TryInitError),
+    #[cfg(not(feature = "otlp"))]
+    #[error("OTLP export requires building the API with the `otlp` feature")]
+    OtlpUnavailable,
+    #[cfg(feature = "otlp")]
+    #[error("Failed to create the OTLP exporter: {0}")]
+    Otlp(#[from] opentelemetry_otlp::ExporterBuildError),
+}
+
+/// Installed logger and trace exporter. Pending traces are flushed when it is dropped.
+pub struct Telemetry {
+    #[cfg(feature = "otlp")]
+    tracer_provider: Option<opentelemetry_sdk::trace::SdkTracerProvider>,
+}
+
+impl Drop for Telemetry {
+    fn drop(&mut self) {
+        #[cfg(feature = "otlp")]
+        if let Some(provider) = self.tracer_provider.take() {
+            if let Err(err) = provider.shutdown() {
+                // The global logger outlives the exporter
+                tracing::error!("Failed to flush traces: {}", err);
+            }
+        }
+    }
+}
+
+fn log_filter() -> EnvFilter {
+    EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER))
+}
+
+/// Logger used while the configuration is loaded, before its log format is known
+pub fn bootstrap_subscriber() -> impl Subscriber + Send + Sync {
+    tracing_subscriber::fmt().with_env_filter(log_filter()).finish()
+}
+
+/// Install the global logger in the configured format, and the OTLP trace
+/// exporter when an endpoint is configured
+///
+/// Spans are logged when they close, with their fields and duration.
+pub fn init(config: &Config) -> Result<Telemetry, TelemetryError> {
+    let fmt = tracing_subscriber::fmt::layer().with_span_events(FmtSpan::CLOSE);
+    let fmt_layer = match config.log_format {
+        LogFormat::Text => fmt.boxed(),
+        LogFormat::Json => fmt.json().with_current_span(true).with_span_list(true).boxed(),
+        LogFormat::Pretty => fmt.pretty().boxed(),
+    };
+
+    #[cfg(feature = "otlp")]
+    let (otlp_layer, tracer_provider) = match &config.otlp_endpoint {
+        Some(endpoint) => {
+            let (layer, provider) = otlp_layer(endpoint)?;
+            (Some(layer), Some(provider))
+        }
+        None => (None, None),
+    };
+    #[cfg(not(feature = "otlp"))]
+    let otlp_layer = match &config.otlp_endpoint {
+        Some(_) => return Err(TelemetryError::OtlpUnavailable),
+        None => None,
+    };
+
+    let layers: Vec<Box<dyn Layer<Registry> + Send + Sync>> = [Some(fmt_layer), otlp_layer].into_iter().flatten().collect();
+    tracing_subscriber::registry().with(layers).with(log_filter()).try_init()?;
+    Ok(Telemetry {
+        #[cfg(feature = "otlp")]
+        tracer_provider,
+    })
+}
+
+/// Layer exporting the spans to an OTLP/HTTP collector
+#[cfg(feature = "otlp")]
+fn otlp_layer(
+    endpoint: &str,
+) -> Result<(Box<dyn Layer<Registry> + Send + Sync>, opentelemetry_sdk::trace::SdkTracerProvider), TelemetryError> {
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_otlp::WithExportConfig;
+
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(format!("{}/v1/traces", endpoint.trim_end_matches('/')))
+        .build()?;
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(opentelemetry_sdk::Resource::builder().with_service_name(env!("CARGO_PKG_NAME")).build())
+        .build();
+    let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer(env!("CARGO_PKG_NAME")));
+    Ok((layer.boxed(), provider))
+}
+
+/// Span of a request, created by `warp::trace`. Its ID, status and duration
+/// are recorded by `request_context` and `finish_request`.
+pub fn request_span(info: warp::trace::Info) -> Span {
+    info_span!(
+        "request",
+        request_id = Empty,
+        method = %info.method(),
+        route = %route_template(info.path()),
+        status = Empty,
+        duration_ms = Empty,
+    )
+}
+
+/// ID and start time of the request being served
+pub struct RequestContext {
+    id: String,
+    started_at: Instant,
+}
+
+/// Take the request ID given by the client, or generate one, and record it
+/// on the request span
+pub fn request_context() -> impl Filter<Extract = (RequestContext,), Error = Infallible> + Clone {
+    warp::header::headers_cloned().map(|headers: HeaderMap| {
+        let id = headers.get(REQUEST_ID_HEADER)
+            .and_then(|id| id.to_str().ok())
+            .filter(|id| is_valid_request_id(id))
+            .map(str::to_string)
+            .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        Span::current().record("request_id", id.as_str());
+        RequestContext { id, started_at: Instant::now() }
+    })
+}
+
+/// Record the status and duration of the request on its span, and return
+/// its ID in the response
+pub fn finish_request(context: RequestContext, reply: impl Reply) -> warp::reply::Response {
+    let mut response = reply.into_response();
+    let span = Span::current();
+    span.record("status", response.status().as_u16());
+    span.record("duration_ms", context.started_at.elapsed().as_millis() as u64);
+    if let Ok(id) = HeaderValue::from_str(&context.id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, id);
+    }
+    response
+}
+
+fn is_valid_request_id(id: &str) -> bool {
+    !id.is_empty() && id.len() <= MAX_REQUEST_ID_LEN && id.bytes().all(|byte| byte.is_ascii_graphic())
+}
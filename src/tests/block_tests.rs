@@ -0,0 +1,276 @@
+/**
+ * Block and Transaction Endpoint Tests
+ *
+ * This module contains tests for the chain data API endpoints:
+ * - /api/blocks/latest - Get the last committed block
+ * - /api/blocks/{height} - Get a block
+ * - /api/blocks/{height}/signatures - Get the signatures of a block
+ * - /api/tx/{hash} - Get an applied transaction
+ *
+ * These tests verify that:
+ * 1. The routes are correctly configured
+ * 2. Input validation works correctly
+ * 3. Transactions are decoded from their block and events
+ * 4. Block signatures are matched to the validators of the block
+ *
+ * Note: Since we're using a mock URL, valid requests get a 503 status code
+ * (as the RPC node can't be reached), and invalid ones a 400.
+ */
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use warp::test::request;
+    use warp::Filter;
+    use namada_core::address::{Address, EstablishedAddress};
+    use namada_core::chain::BlockHeight;
+    use namada_core::tendermint::block::CommitSig;
+    use namada_core::tendermint::{validator, vote, PublicKey, Time};
+    use namada_sdk::events::extend::{ComposeEvent, Height};
+    use namada_sdk::events::{Event, EventLevel};
+    use namada_sdk::gas::event::GasUsed;
+    use namada_sdk::gas::WholeGas;
+    use namada_sdk::rpc::TxAppliedEvents;
+    use namada_sdk::tx::data::{BatchedTxResult, ResultCode, TxResult, TxType};
+    use namada_sdk::tx::either::Either;
+    use namada_sdk::tx::event::{types::APPLIED, Batch, Code};
+    use namada_sdk::tx::Tx;
+    use crate::client::NamadaClient;
+    use crate::events::ChainEvents;
+    use crate::config::parse_rpc_endpoints;
+    use crate::models::block::{BlockSignaturesResponse, SignatureStatus, TxResponse};
+    use crate::models::error::handle_rejection;
+    use crate::AppState;
+    use crate::{get_latest_block, get_block, get_block_signatures, get_tx, with_state};
+
+    /**
+     * Creates a test client with a mock URL.
+     * This client won't connect to a real Namada node.
+     */
+    async fn setup_test_client() -> Arc<AppState> {
+        let namada_client = Arc::new(
+            NamadaClient::with_endpoints(parse_rpc_endpoints("http://mock.example.com").unwrap(), 0)
+                .await
+                .unwrap_or_else(|_| panic!("Failed to create mock client"))
+        );
+
+        Arc::new(AppState {
+            namada_client,
+            validators_details_concurrency: 10,
+            validators_details_timeout: Duration::from_secs(10),
+            events: Arc::new(ChainEvents::new()),
+        })
+    }
+
+    /**
+     * Tests the block endpoints.
+     * Since we're using a mock client, we expect a 503 error, and a 404
+     * for heights that aren't numbers.
+     */
+    #[tokio::test]
+    async fn test_get_block() {
+        let state = setup_test_client().await;
+
+        let latest_block_route = warp::path("api")
+            .and(warp::path("blocks"))
+            .and(warp::path("latest"))
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(get_latest_block);
+
+        let block_route = warp::path("api")
+            .and(warp::path("blocks"))
+            .and(warp::path::param::<u64>())
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(|height: u64, state: Arc<AppState>| async move {
+                get_block(state, height).await
+            });
+
+        let routes = latest_block_route.or(block_route).recover(handle_rejection);
+
+        let response = request()
+            .method("GET")
+            .path("/api/blocks/latest")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 503);
+
+        let response = request()
+            .method("GET")
+            .path("/api/blocks/12345")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 503);
+
+        let response = request()
+            .method("GET")
+            .path("/api/blocks/first")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 404);
+    }
+
+    /**
+     * Tests the block signatures endpoint.
+     * Since we're using a mock client, we expect a 503 error, and a 404
+     * for heights that aren't numbers.
+     */
+    #[tokio::test]
+    async fn test_get_block_signatures() {
+        let state = setup_test_client().await;
+
+        let signatures_route = warp::path("api")
+            .and(warp::path("blocks"))
+            .and(warp::path::param::<u64>())
+            .and(warp::path("signatures"))
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(|height: u64, state: Arc<AppState>| async move {
+                get_block_signatures(state, height).await
+            })
+            .recover(handle_rejection);
+
+        let response = request()
+            .method("GET")
+            .path("/api/blocks/12345/signatures")
+            .reply(&signatures_route)
+            .await;
+        assert_eq!(response.status(), 503);
+
+        let response = request()
+            .method("GET")
+            .path("/api/blocks/latest/signatures")
+            .reply(&signatures_route)
+            .await;
+        assert_eq!(response.status(), 404);
+    }
+
+    /**
+     * Tests matching the signatures of a commit to the validator set of the
+     * block. Validators without a signature are absent.
+     */
+    #[test]
+    fn test_block_signatures() {
+        // Ed25519 public keys of the RFC 8032 test vectors
+        let validator = |key: &str, power: u32| {
+            let bytes: Vec<u8> = (0..key.len())
+                .step_by(2)
+                .map(|i| u8::from_str_radix(&key[i..i + 2], 16).unwrap())
+                .collect();
+            validator::Info::new(PublicKey::from_raw_ed25519(&bytes).unwrap(), vote::Power::from(power))
+        };
+        let validators = [
+            validator("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a", 60),
+            validator("3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c", 30),
+            validator("fc51cd8e6218a1a38da47ed00230f0580816ed13ba3303ac5deb911548908025", 10),
+        ];
+        let signatures = [
+            CommitSig::BlockIdFlagCommit {
+                validator_address: validators[0].address,
+                timestamp: Time::unix_epoch(),
+                signature: None,
+            },
+            CommitSig::BlockIdFlagNil {
+                validator_address: validators[1].address,
+                timestamp: Time::unix_epoch(),
+                signature: None,
+            },
+            CommitSig::BlockIdFlagAbsent,
+        ];
+        let native_address = Address::Established(EstablishedAddress::from([1; 20]));
+        let addresses = HashMap::from([(validators[0].address.to_string(), native_address.clone())]);
+
+        let response = BlockSignaturesResponse::new(12345, true, &validators, &signatures, &addresses);
+        assert_eq!(response.total_voting_power, 100);
+        assert_eq!(response.signed_voting_power, 60);
+        assert_eq!(response.signatures.len(), 3);
+        assert_eq!(response.signatures[0].validator, Some(native_address.to_string()));
+        assert_eq!(response.signatures[0].status, SignatureStatus::Signed);
+        assert_eq!(response.signatures[1].validator, None);
+        assert_eq!(response.signatures[1].status, SignatureStatus::Nil);
+        assert_eq!(response.signatures[2].status, SignatureStatus::Absent);
+        assert_eq!(response.signatures[2].voting_power, 10);
+    }
+
+    /**
+     * Tests the transaction endpoint.
+     * Since we're using a mock client, we expect a 503 error, and a 400
+     * for invalid hashes.
+     */
+    #[tokio::test]
+    async fn test_get_tx() {
+        let state = setup_test_client().await;
+
+        let tx_route = warp::path("api")
+            .and(warp::path("tx"))
+            .and(warp::path::param::<String>())
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(|hash: String, state: Arc<AppState>| async move {
+                get_tx(state, hash).await
+            })
+            .recover(handle_rejection);
+
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/tx/{}", "AB".repeat(32)))
+            .reply(&tx_route)
+            .await;
+        assert_eq!(response.status(), 503);
+
+        let response = request()
+            .method("GET")
+            .path("/api/tx/not_a_hash")
+            .reply(&tx_route)
+            .await;
+        assert_eq!(response.status(), 400);
+    }
+
+    /**
+     * Tests decoding a transaction, with the type of its inner transactions
+     * from their code tags and their status from the batch result.
+     */
+    #[test]
+    fn test_tx_response() {
+        let mut tx = Tx::from_type(TxType::Raw);
+        tx.add_code(vec![1, 2, 3], Some("tx_bond.wasm".to_string()))
+            .add_serialized_data(vec![4, 5, 6]);
+        let commitments = tx.first_commitments().unwrap().clone();
+
+        let mut batch = TxResult::<String>::new();
+        batch.insert_inner_tx_result(None, Either::Right(&commitments), Ok(BatchedTxResult::default()));
+        let applied: Event = Event::new(APPLIED, EventLevel::Tx)
+            .with(Height(BlockHeight(12345)))
+            .with(Code(ResultCode::Ok))
+            .with(GasUsed(WholeGas::from(27000)))
+            .with(Batch(&batch))
+            .into();
+
+        let response = TxResponse::new(&tx, 2, TxAppliedEvents { applied, other: vec![] }).unwrap();
+        assert_eq!(response.hash, tx.header_hash().to_string());
+        assert_eq!(response.height, 12345);
+        assert_eq!(response.index, 2);
+        assert_eq!(response.tx_type, "raw");
+        assert_eq!(response.code, 0);
+        assert_eq!(response.gas_used, 27000);
+        assert_eq!(response.inner_txs.len(), 1);
+        assert_eq!(response.inner_txs[0].tx_type.as_deref(), Some("tx_bond"));
+        assert_eq!(response.inner_txs[0].status, Some("accepted"));
+
+        // Transactions that weren't run have no batch result
+        let applied: Event = Event::new(APPLIED, EventLevel::Tx)
+            .with(Height(BlockHeight(12345)))
+            .with(Code(ResultCode::InvalidSig))
+            .with(GasUsed(WholeGas::from(0)))
+            .into();
+        let response = TxResponse::new(&tx, 0, TxAppliedEvents { applied, other: vec![] }).unwrap();
+        assert_eq!(response.code, 3);
+        assert_eq!(response.inner_txs[0].status, None);
+    }
+}
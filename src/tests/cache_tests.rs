@@ -0,0 +1,166 @@
+/**
+ * Query Cache Tests
+ * 
+ * This module contains tests for the policies of the in-process cache of RPC
+ * query results:
+ * - TTL, epoch-scoped and pinned entries
+ * - Bounded number of entries per query kind
+ * 
+ * These tests verify that:
+ * 1. Entries are only served while they are valid
+ * 2. A new epoch only purges the epoch-scoped kinds
+ * 3. Values fetched while the epoch advanced, and errors, are not cached
+ */
+#[cfg(test)]
+mod tests {
+    use std::hash::Hash;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::{Duration, Instant};
+    use namada_core::address::{Address, EstablishedAddress};
+    use namada_core::chain::{BlockHeight, Epoch};
+    use crate::cache::{CacheMap, CachePolicy, CacheStore, QueryCache, MAX_ENTRIES};
+
+    fn backdate<K: Eq + Hash, V>(map: &CacheMap<K, V>, key: &K, age: Duration) {
+        let mut entries = map.entries.lock().unwrap();
+        let entry = entries.get_mut(key).unwrap();
+        entry.inserted_at = Instant::now().checked_sub(age).unwrap();
+    }
+
+    fn fetch<V>(value: V) -> impl FnOnce() -> std::future::Ready<Result<V, ()>> {
+        move || std::future::ready(Ok(value))
+    }
+
+    fn len<K, V>(map: &CacheMap<K, V>) -> usize {
+        map.entries.lock().unwrap().len()
+    }
+
+    /**
+     * Tests that entries are served until their TTL elapses, then dropped.
+     */
+    #[test]
+    fn test_ttl() {
+        let map = CacheMap::new("test", CachePolicy::ttl(Duration::from_millis(100)));
+        map.insert(1, "value", 0);
+        assert_eq!(map.get(&1, 0), Some("value"));
+        // Only epoch-scoped kinds expire with the epoch
+        assert_eq!(map.get(&1, 1), Some("value"));
+
+        backdate(&map, &1, Duration::from_millis(100));
+        assert_eq!(map.get(&1, 1), None);
+        assert_eq!(len(&map), 0);
+        let (_, stats) = map.stats();
+        assert_eq!((stats.hits, stats.misses), (2, 1));
+    }
+
+    /**
+     * Tests that pinned entries never expire, whatever their age or the epoch.
+     */
+    #[test]
+    fn test_pinned() {
+        let map = CacheMap::new("test", CachePolicy::pinned());
+        map.insert(1, "value", 0);
+        backdate(&map, &1, Duration::from_secs(1));
+        map.purge_epoch_scoped();
+        assert_eq!(map.get(&1, 42), Some("value"));
+    }
+
+    /**
+     * Tests that a new epoch only purges the epoch-scoped kinds, and that
+     * epoch-scoped entries of a past epoch aren't served.
+     */
+    #[tokio::test]
+    async fn test_observe_epoch() {
+        let cache = QueryCache::default();
+        let address = Address::Established(EstablishedAddress::from([1; 20]));
+        cache.observe_epoch(Epoch(1));
+        cache.get_or_fetch(&cache.validators, Epoch(1), fetch(vec![address.clone()])).await.unwrap();
+        cache.get_or_fetch(&cache.native_token, (), fetch(address.clone())).await.unwrap();
+        cache.get_or_fetch(&cache.epoch_at_height, BlockHeight(100), fetch(Epoch(1))).await.unwrap();
+
+        // An epoch already observed purges nothing
+        cache.observe_epoch(Epoch(1));
+        cache.observe_epoch(Epoch(0));
+        assert_eq!(len(&cache.validators), 1);
+
+        cache.observe_epoch(Epoch(2));
+        assert_eq!(len(&cache.validators), 0);
+        assert_eq!(len(&cache.native_token), 1);
+        assert_eq!(len(&cache.epoch_at_height), 1);
+        assert_eq!(cache.stats().epoch, 2);
+
+        let map = CacheMap::new("test", CachePolicy::epoch(Duration::from_secs(60)));
+        map.insert(1, "value", 1);
+        assert_eq!(map.get(&1, 1), Some("value"));
+        assert_eq!(map.get(&1, 2), None);
+    }
+
+    /**
+     * Tests that a full kind drops its expired entries first, then its oldest
+     * entry.
+     */
+    #[test]
+    fn test_max_entries() {
+        let map = CacheMap::new("test", CachePolicy::ttl(Duration::from_secs(1)));
+        for key in 0..MAX_ENTRIES {
+            map.insert(key, key, 0);
+        }
+        backdate(&map, &5, Duration::from_millis(500));
+        backdate(&map, &7, Duration::from_secs(2));
+
+        map.insert(MAX_ENTRIES, MAX_ENTRIES, 0);
+        assert_eq!(len(&map), MAX_ENTRIES);
+        assert_eq!(map.get(&7, 0), None);
+        assert_eq!(map.get(&5, 0), Some(5));
+
+        map.insert(MAX_ENTRIES + 1, MAX_ENTRIES + 1, 0);
+        assert_eq!(len(&map), MAX_ENTRIES);
+        assert_eq!(map.get(&5, 0), None);
+        assert_eq!(map.get(&(MAX_ENTRIES + 1), 0), Some(MAX_ENTRIES + 1));
+
+        // Replacing an entry evicts nothing
+        map.insert(0, 0, 0);
+        assert_eq!(len(&map), MAX_ENTRIES);
+    }
+
+    /**
+     * Tests that values of epoch-scoped kinds aren't cached when the epoch
+     * advanced while they were fetched, unlike values of other kinds.
+     */
+    #[tokio::test]
+    async fn test_epoch_advanced_while_fetching() {
+        let cache = QueryCache::default();
+        cache.observe_epoch(Epoch(1));
+
+        let validators = cache.get_or_fetch(&cache.validators, Epoch(1), || async {
+            cache.observe_epoch(Epoch(2));
+            Ok::<_, ()>(Vec::new())
+        }).await;
+        assert_eq!(validators, Ok(Vec::new()));
+        assert_eq!(len(&cache.validators), 0);
+
+        let epoch = cache.get_or_fetch(&cache.epoch_at_height, BlockHeight(100), || async {
+            cache.observe_epoch(Epoch(3));
+            Ok::<_, ()>(Epoch(1))
+        }).await;
+        assert_eq!(epoch, Ok(Epoch(1)));
+        assert_eq!(len(&cache.epoch_at_height), 1);
+    }
+
+    /**
+     * Tests that errors are returned without being cached.
+     */
+    #[tokio::test]
+    async fn test_errors_not_cached() {
+        let cache = QueryCache::default();
+        let fetches = AtomicU64::new(0);
+        let fetch = || async {
+            fetches.fetch_add(1, Ordering::Relaxed);
+            Err::<Epoch, _>("unavailable")
+        };
+
+        assert_eq!(cache.get_or_fetch(&cache.epoch_at_height, BlockHeight(100), fetch).await, Err("unavailable"));
+        assert_eq!(cache.get_or_fetch(&cache.epoch_at_height, BlockHeight(100), fetch).await, Err("unavailable"));
+        assert_eq!(fetches.load(Ordering::Relaxed), 2);
+        assert_eq!(len(&cache.epoch_at_height), 0);
+    }
+}
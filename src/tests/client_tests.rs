@@ -0,0 +1,115 @@
+/**
+ * RPC Client Tests
+ * 
+ * This module contains tests for the failover of the client among its RPC
+ * endpoints, over synthetic endpoint health:
+ * - Weighted round robin among the endpoints of the lowest priority
+ * - Ordering of the unhealthy and lagging endpoints
+ * - Classification of the errors that are failed over
+ * 
+ * These tests verify that:
+ * 1. Queries are spread in proportion to the weight of the endpoints
+ * 2. Unusable endpoints are only tried last
+ * 3. Only errors of the connection are failed over
+ */
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use namada_core::chain::BlockHeight;
+    use namada_sdk::error::QueryError;
+    use crate::client::{ClientError, Endpoint, EndpointHealth, NamadaClient};
+    use crate::config::RpcEndpointConfig;
+
+    /**
+     * Creates a client of endpoints `(url, priority, weight)`, without
+     * connecting to them.
+     */
+    async fn client(endpoints: &[(&str, u32, u32)], max_lag_blocks: u64) -> NamadaClient {
+        let endpoints = endpoints.iter()
+            .map(|&(url, priority, weight)| RpcEndpointConfig { url: url.to_string(), priority, weight })
+            .collect();
+        NamadaClient::with_endpoints(endpoints, max_lag_blocks).await.unwrap()
+    }
+
+    fn set_health(client: &NamadaClient, url: &str, healthy: bool, height: Option<u64>) {
+        let endpoint = client.endpoints.iter().find(|endpoint| endpoint.url == url).unwrap();
+        *endpoint.health.lock().unwrap() = EndpointHealth { healthy, height: height.map(BlockHeight), error: None };
+    }
+
+    fn urls(candidates: Vec<&Endpoint>) -> Vec<&str> {
+        candidates.into_iter().map(|endpoint| endpoint.url.as_str()).collect()
+    }
+
+    /**
+     * Tests that queries are spread among the endpoints of the lowest
+     * priority in proportion to their weight, and that reading the RPC URL
+     * doesn't advance the rotation.
+     */
+    #[tokio::test]
+    async fn test_candidates_weights() {
+        let client = client(&[("http://a.example.com", 0, 1), ("http://b.example.com", 0, 3), ("http://c.example.com", 1, 5)], 10).await;
+
+        let mut firsts = HashMap::new();
+        for _ in 0..400 {
+            let candidates = urls(client.candidates());
+            assert_eq!(candidates.len(), 3);
+            assert_eq!(candidates[2], "http://c.example.com");
+            *firsts.entry(candidates[0]).or_insert(0) += 1;
+        }
+        assert_eq!(firsts, HashMap::from([("http://a.example.com", 100), ("http://b.example.com", 300)]));
+
+        let rpc_url = client.rpc_url().to_string();
+        assert_eq!(client.rpc_url(), rpc_url);
+        assert_eq!(urls(client.candidates())[0], rpc_url);
+    }
+
+    /**
+     * Tests that unhealthy endpoints, and those lagging too far behind the
+     * highest one, are only tried after the usable ones, whatever their
+     * priority.
+     */
+    #[tokio::test]
+    async fn test_candidates_unusable() {
+        let client = client(&[("http://a.example.com", 0, 1), ("http://b.example.com", 0, 1), ("http://c.example.com", 1, 1)], 10).await;
+        set_health(&client, "http://a.example.com", true, Some(100));
+        set_health(&client, "http://b.example.com", true, Some(80));
+        set_health(&client, "http://c.example.com", true, Some(95));
+        for _ in 0..4 {
+            assert_eq!(urls(client.candidates()), vec!["http://a.example.com", "http://c.example.com", "http://b.example.com"]);
+        }
+
+        set_health(&client, "http://a.example.com", false, Some(100));
+        for _ in 0..4 {
+            assert_eq!(urls(client.candidates()), vec!["http://c.example.com", "http://a.example.com", "http://b.example.com"]);
+        }
+    }
+
+    /**
+     * Tests that when no endpoint is usable, they are all tried by priority.
+     */
+    #[tokio::test]
+    async fn test_candidates_all_unhealthy() {
+        let client = client(&[("http://a.example.com", 2, 1), ("http://b.example.com", 0, 3), ("http://c.example.com", 1, 1)], 10).await;
+        for url in ["http://a.example.com", "http://b.example.com", "http://c.example.com"] {
+            set_health(&client, url, false, None);
+        }
+        for _ in 0..4 {
+            assert_eq!(urls(client.candidates()), vec!["http://b.example.com", "http://c.example.com", "http://a.example.com"]);
+        }
+        assert_eq!(client.rpc_url(), "http://b.example.com");
+    }
+
+    /**
+     * Tests telling errors of the connection from errors answered by the node.
+     */
+    #[test]
+    fn test_is_connection_error() {
+        let no_response = |message: &str| ClientError::from(namada_sdk::error::Error::from(QueryError::NoResponse(message.to_string())));
+        assert!(no_response("error sending request for url (http://mock.example.com/)").is_connection_error());
+        assert!(!no_response("Info log: Unknown query path, error code: 1").is_connection_error());
+        assert!(ClientError::CometBft(tendermint_rpc::Error::channel_send()).is_connection_error());
+        assert!(!ClientError::CometBft(tendermint_rpc::Error::parse("invalid JSON".to_string())).is_connection_error());
+        assert!(!ClientError::Decoding(std::io::Error::other("invalid data")).is_connection_error());
+        assert!(!ClientError::Router(namada_io::client::Error::Query("not found".to_string(), 1)).is_connection_error());
+    }
+}
@@ -0,0 +1,222 @@
+/**
+ * Configuration Tests
+ * 
+ * This module contains tests for the parsing of configuration values:
+ * - NAMADA_RPC_URL / --rpc-url - List of RPC endpoints with priority and weight
+ * - --config - TOML or YAML config file, with the CORS allowlist
+ * - --bind-address, --tls-cert-path, --tls-key-path - Listen address and TLS
+ * - --log-format, --otlp-endpoint - Logging and trace export
+ * - rate_limits, api_keys - Rate limiting quotas and API keys
+ * - uptime_monitor - Windows, watched validators and webhooks of the uptime monitor
+ * 
+ * These tests verify that:
+ * 1. Valid values are parsed with the right defaults
+ * 2. Invalid values are rejected
+ */
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use namada_core::address::{Address, EstablishedAddress};
+    use crate::config::{parse_rpc_endpoints, CliArgs, Config, LogFormat, RateQuota, RpcEndpointConfig};
+
+    /**
+     * Writes a config file to the temporary directory and returns its path.
+     */
+    fn write_config_file(name: &str, contents: &str) -> std::path::PathBuf {
+        let path = std::env::temp_dir().join(format!("namada-api-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).expect("Failed to write config file");
+        path
+    }
+
+    /**
+     * Tests parsing a list of RPC endpoints.
+     * Priority defaults to 0 and weight to 1.
+     */
+    #[test]
+    fn test_parse_rpc_endpoints() {
+        let endpoints = parse_rpc_endpoints("https://rpc-1.example.com|0|3, https://rpc-2.example.com|1,http://localhost:26657")
+            .expect("Failed to parse RPC endpoints");
+        
+        assert_eq!(endpoints, vec![
+            RpcEndpointConfig { url: "https://rpc-1.example.com".to_string(), priority: 0, weight: 3 },
+            RpcEndpointConfig { url: "https://rpc-2.example.com".to_string(), priority: 1, weight: 1 },
+            RpcEndpointConfig { url: "http://localhost:26657".to_string(), priority: 0, weight: 1 },
+        ]);
+    }
+
+    /**
+     * Tests that invalid RPC endpoint lists are rejected.
+     */
+    #[test]
+    fn test_parse_invalid_rpc_endpoints() {
+        // Empty list
+        assert!(parse_rpc_endpoints(" , ").is_err());
+        
+        // Invalid URL
+        assert!(parse_rpc_endpoints("rpc-1.example.com").is_err());
+        
+        // Invalid priority
+        assert!(parse_rpc_endpoints("https://rpc-1.example.com|high").is_err());
+        
+        // Zero weight
+        assert!(parse_rpc_endpoints("https://rpc-1.example.com|0|0").is_err());
+        
+        // Too many fields
+        assert!(parse_rpc_endpoints("https://rpc-1.example.com|0|1|2").is_err());
+    }
+
+    /**
+     * Tests loading a YAML config file, with command-line arguments
+     * overriding its values.
+     */
+    #[test]
+    fn test_load_config_file() {
+        let path = write_config_file("config.yaml", r#"
+port: 4000
+bind_address: "::"
+validators_details_concurrency: 4
+log_format: json
+rpc_endpoints:
+  - url: https://rpc-1.example.com
+    weight: 2
+  - url: https://rpc-2.example.com
+    priority: 1
+cors_allowed_origins:
+  - https://explorer.example.com
+cors_allowed_methods: [GET, OPTIONS]
+"#);
+        let args = CliArgs::parse_from(["namada-api", "--config", path.to_str().unwrap(), "--validators-details-concurrency", "8"]);
+        let config = Config::load(args).expect("Failed to load config file");
+        std::fs::remove_file(&path).ok();
+        
+        assert_eq!(config.rpc_endpoints, vec![
+            RpcEndpointConfig { url: "https://rpc-1.example.com".to_string(), priority: 0, weight: 2 },
+            RpcEndpointConfig { url: "https://rpc-2.example.com".to_string(), priority: 1, weight: 1 },
+        ]);
+        assert_eq!(config.bind_address, std::net::IpAddr::from(std::net::Ipv6Addr::UNSPECIFIED));
+        assert_eq!(config.port, 4000);
+        assert_eq!(config.cors_allowed_origins, vec!["https://explorer.example.com"]);
+        assert_eq!(config.cors_allowed_methods, vec!["GET", "OPTIONS"]);
+        assert_eq!(config.cors_allowed_headers, vec!["content-type", "x-api-key", "x-request-id"]);
+        assert_eq!(config.validators_details_concurrency, 8);
+        assert_eq!(config.validators_details_timeout_ms, 10_000);
+        assert_eq!(config.log_format, LogFormat::Json);
+        assert_eq!(config.otlp_endpoint, None);
+        assert!(config.rate_limit_enabled);
+        assert_eq!(config.rate_limits.heavy, Some(RateQuota { requests_per_second: 1.0, burst: 5 }));
+        assert!(config.api_keys.is_empty());
+        assert!(!config.uptime_monitor.enabled);
+        assert_eq!(config.uptime_monitor.windows, vec![100, 1_000]);
+    }
+
+    /**
+     * Tests that an invalid CORS allowlist in a TOML config file is rejected.
+     */
+    #[test]
+    fn test_load_invalid_cors_config() {
+        let path = write_config_file("config.toml", r#"
+cors_allowed_origins = ["https://explorer.example.com/app"]
+"#);
+        let args = CliArgs::parse_from(["namada-api", "--config", path.to_str().unwrap()]);
+        let result = Config::load(args);
+        std::fs::remove_file(&path).ok();
+        
+        assert!(result.is_err());
+    }
+
+    /**
+     * Tests that rate limiting quotas of 0, API keys used twice and invalid
+     * trusted proxies are rejected.
+     */
+    #[test]
+    fn test_load_invalid_rate_limit_config() {
+        let path = write_config_file("rate-limits-burst.toml", r#"
+[rate_limits.heavy]
+requests_per_second = 1
+burst = 0
+"#);
+        let args = CliArgs::parse_from(["namada-api", "--config", path.to_str().unwrap()]);
+        let result = Config::load(args);
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_err());
+        
+        let path = write_config_file("rate-limits-keys.toml", r#"
+[[api_keys]]
+name = "explorer"
+key = "shared-key"
+
+[[api_keys]]
+name = "wallet"
+key = "shared-key"
+"#);
+        let args = CliArgs::parse_from(["namada-api", "--config", path.to_str().unwrap()]);
+        let result = Config::load(args);
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_err());        
+        let path = write_config_file("rate-limits-proxies.toml", r#"
+trusted_proxies = ["10.0.0.0/33"]
+"#);
+        let args = CliArgs::parse_from(["namada-api", "--config", path.to_str().unwrap()]);
+        let result = Config::load(args);
+        std::fs::remove_file(&path).ok();
+        assert!(result.is_err());
+    }
+
+    /**
+     * Tests loading the uptime monitor configuration, and that windows of 0
+     * blocks, invalid webhooks and invalid validator addresses are rejected.
+     */
+    #[test]
+    fn test_load_uptime_monitor_config() {
+        let validator = Address::Established(EstablishedAddress::from([1; 20]));
+        let path = write_config_file("uptime-monitor.toml", &format!(r#"
+[uptime_monitor]
+enabled = true
+windows = [50, 500]
+validators = ["{}"]
+alert_margin = 0.02
+webhooks = ["https://hooks.example.com/uptime"]
+"#, validator));
+        let args = CliArgs::parse_from(["namada-api", "--config", path.to_str().unwrap()]);
+        let config = Config::load(args).expect("Failed to load config file");
+        std::fs::remove_file(&path).ok();
+        
+        assert!(config.uptime_monitor.enabled);
+        assert_eq!(config.uptime_monitor.poll_interval_ms, 6_000);
+        assert_eq!(config.uptime_monitor.windows, vec![50, 500]);
+        assert_eq!(config.uptime_monitor.validators, vec![validator]);
+        assert_eq!(config.uptime_monitor.alert_margin, 0.02);
+        assert_eq!(config.uptime_monitor.webhooks, vec!["https://hooks.example.com/uptime"]);
+        
+        for (name, contents) in [
+            ("uptime-monitor-window.toml", "[uptime_monitor]\nwindows = [0]"),
+            ("uptime-monitor-webhook.toml", "[uptime_monitor]\nwebhooks = [\"hooks.example.com\"]"),
+            ("uptime-monitor-validator.toml", "[uptime_monitor]\nvalidators = [\"tnam1invalid\"]"),
+        ] {
+            let path = write_config_file(name, contents);
+            let args = CliArgs::parse_from(["namada-api", "--config", path.to_str().unwrap()]);
+            let result = Config::load(args);
+            std::fs::remove_file(&path).ok();
+            assert!(result.is_err(), "{} should be rejected", name);
+        }
+    }
+
+    /**
+     * Tests that a TLS certificate without a key, an invalid bind address,
+     * an unknown log format and an invalid OTLP endpoint are rejected.
+     */
+    #[test]
+    fn test_load_invalid_server_config() {
+        let args = CliArgs::parse_from(["namada-api", "--tls-cert-path", "/etc/namada-api/cert.pem"]);
+        assert!(Config::load(args).is_err());
+        
+        let args = CliArgs::parse_from(["namada-api", "--bind-address", "localhost"]);
+        assert!(Config::load(args).is_err());
+        
+        let args = CliArgs::parse_from(["namada-api", "--log-format", "xml"]);
+        assert!(Config::load(args).is_err());
+        
+        let args = CliArgs::parse_from(["namada-api", "--otlp-endpoint", "localhost:4318"]);
+        assert!(Config::load(args).is_err());
+    }
+}
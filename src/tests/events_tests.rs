@@ -0,0 +1,334 @@
+/**
+ * Chain Events Tests
+ *
+ * This module contains tests for the chain events sent to clients:
+ * - /api/ws - Subscribe to chain events over a WebSocket
+ * - /api/stream/epoch - Server-sent events of the epoch
+ * - /api/stream/validators/{address} - Server-sent events of the state of a validator
+ *
+ * These tests verify that:
+ * 1. Clients only receive the events of the topics they subscribed to
+ * 2. Invalid topics and requests are rejected
+ * 3. Changes of the consensus validator set are computed correctly
+ * 4. Streams resume after the last event received
+ * 5. Validator streams only receive the state of their validator
+ */
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use futures::StreamExt;
+    use warp::test::{request, WsClient};
+    use warp::Filter;
+    use namada_core::address::{Address, EstablishedAddress};
+    use namada_core::chain::Epoch;
+    use namada_core::token::Amount;
+    use serde_json::Value;
+    use crate::client::NamadaClient;
+    use crate::events::{handle_ws_request, new_epochs, validator_set_change, ChainEvents};
+    use crate::config::parse_rpc_endpoints;
+    use crate::models::error::handle_rejection;
+    use crate::models::events::{ChainEvent, LivenessUpdateEvent, NewEpochEvent, Topic, ValidatorSnapshot, WsMessage};
+    use crate::models::pos::ValidatorDetailsResponse;
+    use crate::{stream_epoch, stream_validator, ws_connect, with_state, AppState, WsQuery};
+
+    /**
+     * Creates a test client with a mock URL.
+     * This client won't connect to a real Namada node.
+     */
+    async fn setup_test_client() -> Arc<AppState> {
+        let namada_client = Arc::new(
+            NamadaClient::with_endpoints(parse_rpc_endpoints("http://mock.example.com").unwrap(), 0)
+                .await
+                .unwrap_or_else(|_| panic!("Failed to create mock client"))
+        );
+
+        Arc::new(AppState {
+            namada_client,
+            validators_details_concurrency: 10,
+            validators_details_timeout: Duration::from_secs(10),
+            events: Arc::new(ChainEvents::new()),
+        })
+    }
+
+    fn ws_route(state: Arc<AppState>) -> impl Filter<Extract = (impl warp::Reply,), Error = std::convert::Infallible> + Clone + 'static {
+        warp::path("api")
+            .and(warp::path("ws"))
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(warp::ws())
+            .and(warp::query::<WsQuery>())
+            .and(with_state(state))
+            .and_then(|ws: warp::ws::Ws, query: WsQuery, state: Arc<AppState>| async move {
+                ws_connect(state, ws, query).await
+            })
+            .recover(handle_rejection)
+    }
+
+    /**
+     * Receives a JSON message from a WebSocket.
+     */
+    async fn recv(client: &mut WsClient) -> Value {
+        let message = client.recv().await.expect("Failed to receive");
+        serde_json::from_str(message.to_str().unwrap()).unwrap()
+    }
+
+    /**
+     * Tests that a WebSocket client receives the events of the topics it
+     * subscribed to, on connection or later, and only those.
+     */
+    #[tokio::test]
+    async fn test_ws_subscription() {
+        let state = setup_test_client().await;
+        let mut client = warp::test::ws()
+            .path("/api/ws?topics=new_epoch")
+            .handshake(ws_route(state.clone()))
+            .await
+            .expect("Failed to connect");
+        assert_eq!(recv(&mut client).await, serde_json::json!({ "type": "subscribed", "topics": ["new_epoch"] }));
+
+        // Events of other topics are not sent
+        state.events.publish(ChainEvent::LivenessUpdate(LivenessUpdateEvent {
+            height: 12345,
+            liveness_window_len: 100,
+            liveness_threshold: "0.9".to_string(),
+            validators: vec![],
+        }));
+        state.events.publish(ChainEvent::NewEpoch(NewEpochEvent { epoch: 42, height: 12345 }));
+        assert_eq!(recv(&mut client).await, serde_json::json!({
+            "type": "event",
+            "topic": "new_epoch",
+            "data": { "epoch": 42, "height": 12345 },
+        }));
+
+        client.send_text(r#"{"action": "subscribe", "topics": ["liveness_update"]}"#).await;
+        assert_eq!(recv(&mut client).await["topics"], serde_json::json!(["new_epoch", "liveness_update"]));
+        client.send_text(r#"{"action": "unsubscribe", "topics": ["new_epoch"]}"#).await;
+        assert_eq!(recv(&mut client).await["topics"], serde_json::json!(["liveness_update"]));
+
+        state.events.publish(ChainEvent::NewEpoch(NewEpochEvent { epoch: 43, height: 12445 }));
+        state.events.publish(ChainEvent::LivenessUpdate(LivenessUpdateEvent {
+            height: 12446,
+            liveness_window_len: 100,
+            liveness_threshold: "0.9".to_string(),
+            validators: vec![],
+        }));
+        let event = recv(&mut client).await;
+        assert_eq!(event["topic"], "liveness_update");
+        assert_eq!(event["data"]["height"], 12446);
+    }
+
+    /**
+     * Tests that unknown topics and requests that aren't WebSocket
+     * handshakes are rejected with a 400.
+     */
+    #[tokio::test]
+    async fn test_ws_invalid_requests() {
+        let state = setup_test_client().await;
+
+        let result = warp::test::ws()
+            .path("/api/ws?topics=new_block,new_slot")
+            .handshake(ws_route(state.clone()))
+            .await;
+        assert!(result.is_err());
+
+        let response = request()
+            .method("GET")
+            .path("/api/ws")
+            .reply(&ws_route(state.clone()))
+            .await;
+        assert_eq!(response.status(), 400);
+    }
+
+    /**
+     * Tests applying the requests of a WebSocket client to its topics.
+     */
+    #[test]
+    fn test_ws_request() {
+        let mut topics = BTreeSet::new();
+        let reply = handle_ws_request(r#"{"action": "subscribe", "topics": ["new_block", "new_epoch"]}"#, &mut topics);
+        assert!(matches!(reply, WsMessage::Subscribed { topics } if topics == vec![Topic::NewBlock, Topic::NewEpoch]));
+
+        let reply = handle_ws_request(r#"{"action": "subscribe", "topics": ["new_slot"]}"#, &mut topics);
+        assert!(matches!(reply, WsMessage::Error { .. }));
+        let reply = handle_ws_request("subscribe", &mut topics);
+        assert!(matches!(reply, WsMessage::Error { .. }));
+
+        handle_ws_request(r#"{"action": "unsubscribe", "topics": ["new_block"]}"#, &mut topics);
+        assert_eq!(topics, BTreeSet::from([Topic::NewEpoch]));
+    }
+
+    /**
+     * Tests computing the validators added to, removed from, and with a new
+     * stake in the consensus validator set.
+     */
+    #[test]
+    fn test_validator_set_change() {
+        let validator = |byte: u8| Address::Established(EstablishedAddress::from([byte; 20]));
+        let previous = HashMap::from([
+            (validator(1), Amount::from(100)),
+            (validator(2), Amount::from(200)),
+            (validator(3), Amount::from(300)),
+        ]);
+        let current = HashMap::from([
+            (validator(1), Amount::from(100)),
+            (validator(2), Amount::from(250)),
+            (validator(4), Amount::from(400)),
+        ]);
+
+        let change = validator_set_change(Epoch(42), &previous, &current).unwrap();
+        assert_eq!(change.epoch, 42);
+        assert_eq!(change.added.len(), 1);
+        assert_eq!(change.added[0].address, validator(4).to_string());
+        assert_eq!(change.removed, vec![validator(3).to_string()]);
+        assert_eq!(change.updated.len(), 1);
+        assert_eq!(change.updated[0].address, validator(2).to_string());
+        assert_eq!(change.updated[0].stake, Amount::from(250).to_string());
+
+        assert!(validator_set_change(Epoch(42), &previous, &previous).is_none());
+    }
+
+    /**
+     * Tests the server-sent events endpoints.
+     * Since we're using a mock client, we expect a 503 error, and a 400 for
+     * invalid addresses and event IDs.
+     */
+    #[tokio::test]
+    async fn test_stream_endpoints() {
+        let state = setup_test_client().await;
+
+        let epoch_route = warp::path("api")
+            .and(warp::path("stream"))
+            .and(warp::path("epoch"))
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(warp::header::optional::<u64>("last-event-id"))
+            .and(with_state(state.clone()))
+            .and_then(|last_event_id: Option<u64>, state: Arc<AppState>| async move {
+                stream_epoch(state, last_event_id).await
+            });
+
+        let validator_route = warp::path("api")
+            .and(warp::path("stream"))
+            .and(warp::path("validators"))
+            .and(warp::path::param::<String>())
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(warp::header::optional::<u64>("last-event-id"))
+            .and(with_state(state.clone()))
+            .and_then(|address: String, last_event_id: Option<u64>, state: Arc<AppState>| async move {
+                stream_validator(state, address, last_event_id).await
+            });
+
+        let routes = epoch_route.or(validator_route).recover(handle_rejection);
+
+        let response = request()
+            .method("GET")
+            .path("/api/stream/epoch")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 503);
+
+        let response = request()
+            .method("GET")
+            .path("/api/stream/epoch")
+            .header("last-event-id", "latest")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 400);
+
+        let validator = Address::Established(EstablishedAddress::from([1; 20]));
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/stream/validators/{}", validator))
+            .header("last-event-id", "12345")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 503);
+
+        let response = request()
+            .method("GET")
+            .path("/api/stream/validators/invalid_address")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 400);
+    }
+
+    /**
+     * Tests that the epoch stream starts with the current epoch, unless it
+     * was the last one received, and skips the epochs already sent.
+     */
+    #[tokio::test]
+    async fn test_new_epochs() {
+        let events = ChainEvents::new();
+        let current = NewEpochEvent { epoch: 42, height: 12345 };
+        let publish = |epoch: u64| events.publish(ChainEvent::NewEpoch(NewEpochEvent { epoch, height: epoch * 100 }));
+
+        let stream = new_epochs(current.clone(), None, events.subscribe());
+        publish(42);
+        publish(43);
+        let epochs: Vec<_> = stream.take(2).collect().await;
+        assert_eq!(epochs, vec![current.clone(), NewEpochEvent { epoch: 43, height: 4300 }]);
+
+        // Resuming from the current epoch
+        let stream = new_epochs(current, Some(42), events.subscribe());
+        publish(44);
+        let epochs: Vec<_> = stream.take(1).collect().await;
+        assert_eq!(epochs, vec![NewEpochEvent { epoch: 44, height: 4400 }]);
+    }
+
+    /**
+     * Tests that validators are watched while their streams are alive, and
+     * that streams only receive the state of their validator.
+     */
+    #[tokio::test]
+    async fn test_watch_validator() {
+        let events = Arc::new(ChainEvents::new());
+        let validator = |byte: u8| Address::Established(EstablishedAddress::from([byte; 20]));
+        let snapshot = |byte: u8, epoch: u64| ValidatorSnapshot {
+            height: epoch * 100,
+            details: ValidatorDetailsResponse {
+                address: validator(byte).to_string(),
+                epoch,
+                state: "Consensus".to_string(),
+                stake: "1000".to_string(),
+                commission_rate: "0.05".to_string(),
+                max_commission_change_per_epoch: "0.01".to_string(),
+                metadata: None,
+            },
+        };
+
+        let stream = events.watch_validator(validator(1));
+        let other_stream = events.watch_validator(validator(1));
+        assert_eq!(events.watched_validators(), vec![validator(1)]);
+        drop(other_stream);
+        assert_eq!(events.watched_validators(), vec![validator(1)]);
+
+        events.publish_validator_snapshot(snapshot(2, 42));
+        events.publish_validator_snapshot(snapshot(1, 43));
+        let snapshots: Vec<_> = stream.take(1).collect().await;
+        assert_eq!(snapshots[0].height, 4300);
+        assert_eq!(snapshots[0].details.address, validator(1).to_string());
+        assert!(events.watched_validators().is_empty());
+    }
+
+    /**
+     * Tests that validators only change with their state, stake or
+     * commission, not with the epoch they were queried at.
+     */
+    #[test]
+    fn test_validator_has_changed() {
+        let details = |epoch: u64, stake: &str| ValidatorDetailsResponse {
+            address: "tnam1q...".to_string(),
+            epoch,
+            state: "Consensus".to_string(),
+            stake: stake.to_string(),
+            commission_rate: "0.05".to_string(),
+            max_commission_change_per_epoch: "0.01".to_string(),
+            metadata: None,
+        };
+        assert!(!details(123, "1000").has_changed(&details(124, "1000")));
+        assert!(details(123, "1000").has_changed(&details(124, "2000")));
+    }
+}
@@ -0,0 +1,326 @@
+/**
+ * Governance Endpoint Tests
+ * 
+ * This module contains tests for the governance API endpoints:
+ * - /api/gov/proposals - List proposals, filtered by status, with pagination
+ * - /api/gov/proposals/{id} - Get a proposal with its tally
+ * - /api/gov/proposals/{id}/votes - List the votes on a proposal
+ * - /api/gov/proposals/{id}/votes/{address} - Get the effective vote of an address
+ * - /api/gov/parameters - Get governance parameters
+ * 
+ * These tests verify that:
+ * 1. The routes are correctly configured
+ * 2. Input validation works correctly
+ * 3. Error handling functions properly
+ * 
+ * Note: Since we're using a mock URL, valid requests get a 503 status code
+ * (as the RPC node can't be reached), and invalid ones a 400.
+ */
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use warp::test::request;
+    use warp::Filter;
+    use namada_core::address::{self, Address, EstablishedAddress};
+    use namada_core::chain::Epoch;
+    use namada_sdk::governance::storage::proposal::{ProposalType, StorageProposal};
+    use namada_sdk::governance::storage::vote::ProposalVote;
+    use namada_sdk::governance::utils::Vote;
+    use crate::client::NamadaClient;
+    use crate::events::ChainEvents;
+    use crate::config::parse_rpc_endpoints;
+    use crate::models::error::handle_rejection;
+    use crate::models::gov::{EffectiveVote, ProposalStatusResponse, ProposalSummaryResponse, ProposalsQuery};
+    use crate::AppState;
+    use crate::{get_proposals, get_proposal, get_proposal_votes, get_effective_vote, get_gov_parameters, with_state};
+
+    /**
+     * Creates a test client with a mock URL.
+     * This client won't connect to a real Namada node.
+     */
+    async fn setup_test_client() -> Arc<AppState> {
+        let namada_client = Arc::new(
+            NamadaClient::with_endpoints(parse_rpc_endpoints("http://mock.example.com").unwrap(), 0)
+                .await
+                .unwrap_or_else(|_| panic!("Failed to create mock client"))
+        );
+        
+        Arc::new(AppState {
+            namada_client,
+            validators_details_concurrency: 10,
+            validators_details_timeout: Duration::from_secs(10),
+            events: Arc::new(ChainEvents::new()),
+        })
+    }
+
+    /**
+     * Tests the proposals endpoint, with its status filter and pagination
+     * validation.
+     */
+    #[tokio::test]
+    async fn test_get_proposals() {
+        let state = setup_test_client().await;
+        
+        let proposals_route = warp::path("api")
+            .and(warp::path("gov"))
+            .and(warp::path("proposals"))
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(warp::query::<ProposalsQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|query: ProposalsQuery, state: Arc<AppState>| async move {
+                get_proposals(state, query).await
+            })
+            .recover(handle_rejection);
+        
+        // Valid parameters, the RPC call fails
+        let response = request()
+            .method("GET")
+            .path("/api/gov/proposals?status=voting&page=1&per_page=10")
+            .reply(&proposals_route)
+            .await;
+        assert_eq!(response.status(), 503);
+        
+        // Unknown status
+        let response = request()
+            .method("GET")
+            .path("/api/gov/proposals?status=passed")
+            .reply(&proposals_route)
+            .await;
+        assert_eq!(response.status(), 400);
+        
+        // Invalid pagination
+        let response = request()
+            .method("GET")
+            .path("/api/gov/proposals?page=0")
+            .reply(&proposals_route)
+            .await;
+        assert_eq!(response.status(), 400);
+        
+        let response = request()
+            .method("GET")
+            .path("/api/gov/proposals?per_page=100")
+            .reply(&proposals_route)
+            .await;
+        assert_eq!(response.status(), 400);
+    }
+
+    /**
+     * Tests the proposal endpoint.
+     * Since we're using a mock client, we expect a 503 error, and a 404
+     * for IDs that aren't numbers.
+     */
+    #[tokio::test]
+    async fn test_get_proposal() {
+        let state = setup_test_client().await;
+        
+        let proposal_route = warp::path("api")
+            .and(warp::path("gov"))
+            .and(warp::path("proposals"))
+            .and(warp::path::param::<u64>())
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(|id: u64, state: Arc<AppState>| async move {
+                get_proposal(state, id).await
+            })
+            .recover(handle_rejection);
+        
+        let response = request()
+            .method("GET")
+            .path("/api/gov/proposals/42")
+            .reply(&proposal_route)
+            .await;
+        assert_eq!(response.status(), 503);
+        
+        let response = request()
+            .method("GET")
+            .path("/api/gov/proposals/latest")
+            .reply(&proposal_route)
+            .await;
+        assert_eq!(response.status(), 404);
+    }
+
+    /**
+     * Tests the votes endpoints.
+     * Since we're using a mock client, we expect a 503 error, and a 400
+     * for invalid addresses.
+     */
+    #[tokio::test]
+    async fn test_get_proposal_votes() {
+        let state = setup_test_client().await;
+        
+        let votes_route = warp::path("api")
+            .and(warp::path("gov"))
+            .and(warp::path("proposals"))
+            .and(warp::path::param::<u64>())
+            .and(warp::path("votes"))
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(|id: u64, state: Arc<AppState>| async move {
+                get_proposal_votes(state, id).await
+            });
+        
+        let vote_route = warp::path("api")
+            .and(warp::path("gov"))
+            .and(warp::path("proposals"))
+            .and(warp::path::param::<u64>())
+            .and(warp::path("votes"))
+            .and(warp::path::param::<String>())
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(|id: u64, address: String, state: Arc<AppState>| async move {
+                get_effective_vote(state, id, address).await
+            });
+        
+        let routes = votes_route.or(vote_route).recover(handle_rejection);
+        
+        let response = request()
+            .method("GET")
+            .path("/api/gov/proposals/42/votes")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 503);
+        
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/gov/proposals/42/votes/{}", address::GOV))
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 503);
+        
+        let response = request()
+            .method("GET")
+            .path("/api/gov/proposals/42/votes/invalid_address")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 400);
+    }
+
+    /**
+     * Tests the effective vote of an address: its own votes when it voted,
+     * otherwise the vote of each of its validators, if any.
+     */
+    #[test]
+    fn test_effective_vote() {
+        let established = |byte: u8| Address::Established(EstablishedAddress::from([byte; 20]));
+        let (validator_1, validator_2, delegator) = (established(1), established(2), established(3));
+        let vote = |validator: &Address, delegator: &Address, data: ProposalVote| Vote {
+            validator: validator.clone(),
+            delegator: delegator.clone(),
+            data,
+        };
+        let validators = [validator_1.clone(), validator_2.clone()];
+        
+        // Only the first validator voted
+        let votes = [vote(&validator_1, &validator_1, ProposalVote::Nay)];
+        let (voted, effective_votes) = EffectiveVote::of(&delegator, &votes, &validators);
+        assert!(!voted);
+        assert_eq!(effective_votes.len(), 2);
+        assert_eq!(effective_votes[0].validator, validator_1.to_string());
+        assert_eq!(effective_votes[0].vote, Some("nay"));
+        assert!(effective_votes[0].inherited);
+        assert_eq!(effective_votes[1].vote, None);
+        
+        // The delegator overrides the vote of its validator
+        let votes = [
+            vote(&validator_1, &validator_1, ProposalVote::Nay),
+            vote(&validator_1, &delegator, ProposalVote::Yay),
+        ];
+        let (voted, effective_votes) = EffectiveVote::of(&delegator, &votes, &[]);
+        assert!(voted);
+        assert_eq!(effective_votes.len(), 1);
+        assert_eq!(effective_votes[0].vote, Some("yay"));
+        assert!(!effective_votes[0].inherited);
+        
+        // A validator votes for itself
+        let (voted, effective_votes) = EffectiveVote::of(&validator_1, &votes, &[]);
+        assert!(voted);
+        assert_eq!(effective_votes[0].vote, Some("nay"));
+    }
+
+    /**
+     * Tests the effective vote of a delegator of two validators that only
+     * voted through one of them: it inherits the vote of the other one.
+     */
+    #[test]
+    fn test_effective_vote_partial() {
+        let established = |byte: u8| Address::Established(EstablishedAddress::from([byte; 20]));
+        let (validator_1, validator_2, delegator) = (established(1), established(2), established(3));
+        let vote = |validator: &Address, delegator: &Address, data: ProposalVote| Vote {
+            validator: validator.clone(),
+            delegator: delegator.clone(),
+            data,
+        };
+        let validators = [validator_1.clone(), validator_2.clone()];
+        let votes = [
+            vote(&validator_1, &validator_1, ProposalVote::Nay),
+            vote(&validator_2, &validator_2, ProposalVote::Nay),
+            vote(&validator_2, &delegator, ProposalVote::Yay),
+        ];
+        
+        let (voted, effective_votes) = EffectiveVote::of(&delegator, &votes, &validators);
+        assert!(voted);
+        assert_eq!(effective_votes.len(), 2);
+        assert_eq!(effective_votes[0].validator, validator_1.to_string());
+        assert_eq!(effective_votes[0].vote, Some("nay"));
+        assert!(effective_votes[0].inherited);
+        assert_eq!(effective_votes[1].validator, validator_2.to_string());
+        assert_eq!(effective_votes[1].vote, Some("yay"));
+        assert!(!effective_votes[1].inherited);
+    }
+
+    /**
+     * Tests the governance parameters endpoint.
+     * Since we're using a mock client, we expect a 500 error.
+     */
+    #[tokio::test]
+    async fn test_get_gov_parameters() {
+        let state = setup_test_client().await;
+        
+        let parameters_route = warp::path("api")
+            .and(warp::path("gov"))
+            .and(warp::path("parameters"))
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(get_gov_parameters);
+        
+        let response = request()
+            .method("GET")
+            .path("/api/gov/parameters")
+            .reply(&parameters_route)
+            .await;
+        assert_eq!(response.status(), 500);
+    }
+
+    /**
+     * Tests the status of a proposal around its voting period.
+     * Voting is open from the start epoch, until the end epoch excluded.
+     */
+    #[test]
+    fn test_proposal_status() {
+        let proposal = StorageProposal {
+            id: 7,
+            content: BTreeMap::from([("title".to_string(), "Increase the validator slots".to_string())]),
+            author: address::GOV,
+            r#type: ProposalType::Default,
+            voting_start_epoch: Epoch(10),
+            voting_end_epoch: Epoch(20),
+            activation_epoch: Epoch(25),
+        };
+        
+        assert_eq!(ProposalStatusResponse::of(&proposal, Epoch(9)), ProposalStatusResponse::Pending);
+        assert_eq!(ProposalStatusResponse::of(&proposal, Epoch(10)), ProposalStatusResponse::Voting);
+        assert_eq!(ProposalStatusResponse::of(&proposal, Epoch(19)), ProposalStatusResponse::Voting);
+        assert_eq!(ProposalStatusResponse::of(&proposal, Epoch(20)), ProposalStatusResponse::Ended);
+        
+        let summary = ProposalSummaryResponse::new(&proposal, Epoch(12));
+        assert_eq!(summary.title.as_deref(), Some("Increase the validator slots"));
+        assert_eq!(summary.proposal_type, "default");
+        assert_eq!(summary.status, ProposalStatusResponse::Voting);
+    }
+}
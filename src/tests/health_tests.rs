@@ -4,6 +4,9 @@
  * This module contains tests for the API health endpoints:
  * - /api/health/api_status - Basic service health check
  * - /api/health/rpc_status - RPC connection health check
+ * - /api/health/cache_status - Query cache statistics
+ * - /metrics - Prometheus metrics
+ * - X-Request-Id - Request ID returned with every response
  * 
  * These tests verify that:
  * 1. The routes are correctly configured
@@ -13,11 +16,16 @@
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
+    use std::time::Duration;
     use warp::test::request;
     use warp::Filter;
     use crate::client::NamadaClient;
+    use crate::events::ChainEvents;
+    use crate::config::parse_rpc_endpoints;
     use crate::AppState;
-    use crate::{health_check, rpc_health_check, with_state};
+    use crate::{health_check, rpc_health_check, cache_status, get_metrics, with_state};
+    use crate::metrics::METRICS;
+    use crate::telemetry;
     use serde_json::Value;
 
     /**
@@ -27,12 +35,17 @@ mod tests {
     async fn setup_test_client() -> Arc<AppState> {
         // This is a mock URL that won't be used in tests
         let namada_client = Arc::new(
-            NamadaClient::new("http://mock.example.com".to_string())
+            NamadaClient::with_endpoints(parse_rpc_endpoints("http://mock.example.com").unwrap(), 0)
                 .await
                 .unwrap_or_else(|_| panic!("Failed to create mock client"))
         );
         
-        Arc::new(AppState { namada_client })
+        Arc::new(AppState {
+            namada_client,
+            validators_details_concurrency: 10,
+            validators_details_timeout: Duration::from_secs(10),
+            events: Arc::new(ChainEvents::new()),
+        })
     }
 
     /**
@@ -66,6 +79,52 @@ mod tests {
         assert!(body["version"].is_string());
     }
 
+    /**
+     * Tests that the request ID given by the client is returned, and that
+     * one is generated when it is missing or invalid.
+     */
+    #[tokio::test]
+    async fn test_request_id() {
+        let health_route = warp::path("api")
+            .and(warp::path("health"))
+            .and(warp::path("api_status"))
+            .and(warp::get())
+            .and_then(health_check);
+        let health_route = telemetry::request_context()
+            .and(health_route)
+            .map(telemetry::finish_request)
+            .with(warp::trace(telemetry::request_span));
+        
+        // Request ID given by the client
+        let response = request()
+            .method("GET")
+            .path("/api/health/api_status")
+            .header("x-request-id", "explorer-42")
+            .reply(&health_route)
+            .await;
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["x-request-id"], "explorer-42");
+        
+        // Generated request IDs
+        let response = request()
+            .method("GET")
+            .path("/api/health/api_status")
+            .reply(&health_route)
+            .await;
+        let generated = response.headers()["x-request-id"].to_str().unwrap().to_string();
+        assert_eq!(generated.len(), 36);
+        
+        let response = request()
+            .method("GET")
+            .path("/api/health/api_status")
+            .header("x-request-id", "two words")
+            .reply(&health_route)
+            .await;
+        let regenerated = response.headers()["x-request-id"].to_str().unwrap();
+        assert_eq!(regenerated.len(), 36);
+        assert_ne!(regenerated, generated);
+    }
+
     /**
      * Tests the RPC health check endpoint.
      * Since we're using a mock URL, we expect this to return an error (500 or 503).
@@ -93,4 +152,72 @@ mod tests {
         // But we can still check that the response has the right structure
         assert!(response.status() == 500 || response.status() == 503);
     }
-} 
\ No newline at end of file
+
+    /**
+     * Tests the cache statistics endpoint.
+     * A failed query counts as a miss, and its error is not cached.
+     */
+    #[tokio::test]
+    async fn test_cache_status() {
+        let state = setup_test_client().await;
+        assert!(state.namada_client.query_native_token().await.is_err());
+        
+        // Create the filter for testing
+        let cache_status_route = warp::path("api")
+            .and(warp::path("health"))
+            .and(warp::path("cache_status"))
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(cache_status);
+        
+        // Test the endpoint
+        let response = request()
+            .method("GET")
+            .path("/api/health/cache_status")
+            .reply(&cache_status_route)
+            .await;
+        
+        assert_eq!(response.status(), 200);
+        
+        let body: Value = serde_json::from_slice(response.body()).expect("Failed to parse JSON");
+        assert_eq!(body["epoch"], 0);
+        assert_eq!(body["kinds"]["native_token"]["hits"], 0);
+        assert_eq!(body["kinds"]["native_token"]["misses"], 1);
+        assert_eq!(body["kinds"]["native_token"]["entries"], 0);
+        assert!(body["kinds"]["validator_sets"].is_object());
+    }
+
+    /**
+     * Tests the Prometheus metrics endpoint.
+     * Request paths are reported with their parameters replaced by `{param}`.
+     */
+    #[tokio::test]
+    async fn test_metrics() {
+        let state = setup_test_client().await;
+        METRICS.record_request("/api/pos/validator_details/tnam1qxyz", "GET", 200, Duration::from_millis(5));
+        assert!(state.namada_client.query_native_token().await.is_err());
+        
+        // Create the filter for testing
+        let metrics_route = warp::path("metrics")
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(with_state(state.clone()))
+            .and_then(get_metrics);
+        
+        // Test the endpoint
+        let response = request()
+            .method("GET")
+            .path("/metrics")
+            .reply(&metrics_route)
+            .await;
+        
+        assert_eq!(response.status(), 200);
+        
+        let body = String::from_utf8(response.body().to_vec()).expect("Metrics are not UTF-8");
+        assert!(body.contains(r#"namada_api_http_requests_total{method="GET",route="/api/pos/validator_details/{param}",status="200"}"#));
+        assert!(body.contains(r#"namada_api_rpc_errors_total{method="query_native_token"}"#));
+        assert!(body.contains(r#"namada_api_cache_hit_ratio{kind="native_token"}"#));
+        assert!(body.contains("namada_api_chain_epoch"));
+        assert!(!body.contains("tnam1qxyz"));
+    }
+}
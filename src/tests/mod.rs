@@ -6,10 +6,25 @@
  * 
  * - health_tests: Tests for health and status endpoints
  * - pos_tests: Tests for Proof of Stake related endpoints
+ * - gov_tests: Tests for governance endpoints
+ * - block_tests: Tests for block and transaction endpoints
+ * - events_tests: Tests for the WebSocket and server-sent chain events
+ * - config_tests: Tests for the parsing of configuration values
+ * - rate_limit_tests: Tests for the rate limiting of requests
+ * - uptime_tests: Tests for the tracking of the uptime of validators
+ * - cache_tests: Tests for the policies of the query cache
+ * - client_tests: Tests for the failover among the RPC endpoints
  * 
  * See the README.md file in this directory for more details on the testing approach.
  */
-
 // Test modules
 pub mod health_tests;
-pub mod pos_tests; 
\ No newline at end of file
+pub mod pos_tests;
+pub mod gov_tests;
+pub mod block_tests;
+pub mod events_tests;
+pub mod config_tests;
+pub mod rate_limit_tests;
+pub mod uptime_tests;
+pub mod cache_tests;
+pub mod client_tests;
\ No newline at end of file
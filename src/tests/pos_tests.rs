@@ -9,6 +9,18 @@
  * - /api/pos/liveness_info - Get validator liveness information
  * - /api/pos/validator_set/consensus - Get consensus validator set
  * - /api/pos/validator_set/below_capacity - Get below-capacity validator set
+ * - /api/pos/delegations/{address} - Get delegations of an address
+ * - /api/pos/bonds/{source}/to/{validator} - Get bonds between a source and a validator
+ * - /api/pos/unbonds/{source}/to/{validator} - Get unbonds between a source and a validator
+ * - /api/pos/withdrawable/{source}/{validator} - Get withdrawable tokens at a validator
+ * - /api/pos/withdrawable/{source} - Get withdrawable tokens across all validators
+ * - /api/pos/rewards/{validator}/{delegator} - Get unclaimed rewards of a delegator
+ * - /api/pos/rewards/{validator} - Get unclaimed self-bond rewards of a validator
+ * - /api/pos/total_rewards/{delegator} - Get unclaimed rewards across all validators
+ * - /api/pos/validators/{address}/slashes - Get slashes applied to a validator
+ * - /api/pos/slashes - Get slashes applied to all validators
+ * - /api/pos/total_stake - Get total bonded stake and staking ratio
+ * - /api/pos/parameters - Get PoS parameters
  * 
  * These tests verify that:
  * 1. The routes are correctly configured
@@ -22,13 +34,21 @@
 #[cfg(test)]
 mod tests {
     use std::sync::Arc;
+    use std::time::Duration;
     use warp::test::request;
     use warp::Filter;
     use crate::client::NamadaClient;
+    use crate::events::ChainEvents;
+    use crate::config::parse_rpc_endpoints;
+    use crate::models::error::handle_rejection;
     use crate::AppState;
     use crate::{get_all_validators, get_liveness_info, get_validator_by_tm_addr,
         get_validator_details, get_validators_details, get_consensus_validator_set,
-        get_below_capacity_validator_set, with_state, ValidatorsQuery};
+        get_below_capacity_validator_set, get_delegations, get_bonds, get_unbonds, get_withdrawable,
+        get_source_withdrawable, withdrawal_schedule, get_rewards, get_total_rewards,
+        get_validator_slashes, get_all_slashes, get_total_stake, staking_ratio,
+        get_pos_parameters, with_state, ValidatorsQuery,
+        EpochQuery};
 
     /**
      * Helper function to create a sample validator address for testing.
@@ -45,17 +65,23 @@ mod tests {
     async fn setup_test_client() -> Arc<AppState> {
         // This is a mock URL that won't be used in real tests
         let namada_client = Arc::new(
-            NamadaClient::new("http://mock.example.com".to_string())
+            NamadaClient::with_endpoints(parse_rpc_endpoints("http://mock.example.com").unwrap(), 0)
                 .await
                 .unwrap_or_else(|_| panic!("Failed to create mock client"))
         );
         
-        Arc::new(AppState { namada_client })
+        Arc::new(AppState {
+            namada_client,
+            validators_details_concurrency: 10,
+            validators_details_timeout: Duration::from_secs(10),
+            events: Arc::new(ChainEvents::new()),
+        })
     }
 
     /**
      * Tests the endpoint to get all validators.
-     * Since we're using a mock client, we expect a 500 error.
+     * Since we're using a mock client, we expect a 500 error,
+     * with or without an epoch. A malformed epoch is rejected.
      */
     #[tokio::test]
     async fn test_get_all_validators() {
@@ -65,9 +91,13 @@ mod tests {
         let validators_route = warp::path("api")
             .and(warp::path("pos"))
             .and(warp::path("validators"))
+            .and(warp::path::end())
             .and(warp::get())
+            .and(warp::query::<EpochQuery>())
             .and(with_state(state.clone()))
-            .and_then(get_all_validators);
+            .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+                get_all_validators(state, query).await
+            });
         
         // Test the endpoint
         let response = request()
@@ -78,6 +108,24 @@ mod tests {
         
         // The response should be an error, since we're using a mock URL
         assert!(response.status() == 500);
+
+        // Test with a historical epoch
+        let response = request()
+            .method("GET")
+            .path("/api/pos/validators?epoch=5")
+            .reply(&validators_route)
+            .await;
+        
+        assert!(response.status() == 500);
+
+        // Test with a malformed epoch
+        let response = request()
+            .method("GET")
+            .path("/api/pos/validators?epoch=abc")
+            .reply(&validators_route)
+            .await;
+        
+        assert!(response.status() == 400);
     }
 
     /**
@@ -96,9 +144,10 @@ mod tests {
             .and(warp::path("validators"))
             .and(warp::path::param::<String>())
             .and(warp::get())
+            .and(warp::query::<EpochQuery>())
             .and(with_state(state.clone()))
-            .and_then(|address: String, state: Arc<AppState>| async move {
-                get_validator_details(state, address).await
+            .and_then(|address: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_validator_details(state, address, query).await
             });
         
         // Test with a valid-format address
@@ -179,6 +228,53 @@ mod tests {
         assert!(response.status() == 400 || response.status() == 500);
     }
 
+    /**
+     * Tests that a validators_details request fails with a 504 once its
+     * deadline passes, against an RPC endpoint that never replies.
+     */
+    #[tokio::test]
+    async fn test_get_validators_details_timeout() {
+        // Accept connections without ever replying to them
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((connection, _)) = listener.accept().await {
+                connections.push(connection);
+            }
+        });
+
+        let namada_client = Arc::new(
+            NamadaClient::with_endpoints(parse_rpc_endpoints(&rpc_url).unwrap(), 0)
+                .await
+                .unwrap_or_else(|_| panic!("Failed to create client"))
+        );
+        let state = Arc::new(AppState {
+            namada_client,
+            validators_details_concurrency: 10,
+            validators_details_timeout: Duration::from_millis(50),
+            events: Arc::new(ChainEvents::new()),
+        });
+
+        let validators_details_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("validators_details"))
+            .and(warp::get())
+            .and(warp::query::<ValidatorsQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|query: ValidatorsQuery, state: Arc<AppState>| async move {
+                get_validators_details(state, query).await
+            })
+            .recover(handle_rejection);
+
+        let response = request()
+            .method("GET")
+            .path("/api/pos/validators_details?page=1&per_page=10")
+            .reply(&validators_details_route)
+            .await;
+        assert_eq!(response.status(), 504);
+    }
+
     /**
      * Tests finding a validator by Tendermint address.
      * This test verifies:
@@ -235,8 +331,11 @@ mod tests {
             .and(warp::path("pos"))
             .and(warp::path("liveness_info"))
             .and(warp::get())
+            .and(warp::query::<EpochQuery>())
             .and(with_state(state.clone()))
-            .and_then(get_liveness_info);
+            .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+                get_liveness_info(state, query).await
+            });
         
         // Test the endpoint
         let response = request()
@@ -263,8 +362,11 @@ mod tests {
             .and(warp::path("validator_set"))
             .and(warp::path("consensus"))
             .and(warp::get())
+            .and(warp::query::<EpochQuery>())
             .and(with_state(state.clone()))
-            .and_then(get_consensus_validator_set);
+            .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+                get_consensus_validator_set(state, query).await
+            });
         
         // Test the endpoint
         let response = request()
@@ -291,8 +393,11 @@ mod tests {
             .and(warp::path("validator_set"))
             .and(warp::path("below_capacity"))
             .and(warp::get())
+            .and(warp::query::<EpochQuery>())
             .and(with_state(state.clone()))
-            .and_then(get_below_capacity_validator_set);
+            .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+                get_below_capacity_validator_set(state, query).await
+            });
         
         // Test the endpoint
         let response = request()
@@ -304,4 +409,601 @@ mod tests {
         // The response should be an error since we're using a mock URL
         assert!(response.status() == 500);
     }
-} 
\ No newline at end of file
+
+    /**
+     * Tests retrieving the delegations of an address.
+     * This test verifies:
+     * 1. Proper handling of a valid address, with and without an epoch (expect 500 with mock client)
+     * 2. Proper rejection of invalid address format
+     */
+    #[tokio::test]
+    async fn test_get_delegations() {
+        let state = setup_test_client().await;
+        
+        // Create the filter for testing
+        let delegations_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("delegations"))
+            .and(warp::path::param::<String>())
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|address: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_delegations(state, address, query).await
+            });
+        
+        // Test with a valid-format address
+        let valid_address = sample_address(1);
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/delegations/{}", valid_address))
+            .reply(&delegations_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        // Test with an explicit epoch
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/delegations/{}?epoch=10", valid_address))
+            .reply(&delegations_route)
+            .await;
+        
+        assert!(response.status() == 500);
+
+        // Test with an invalid address
+        let response = request()
+            .method("GET")
+            .path("/api/pos/delegations/invalid-address")
+            .reply(&delegations_route)
+            .await;
+        
+        // Should be either 400 Bad Request or 500 Internal Server Error
+        // depending on how address validation is implemented
+        assert!(response.status() == 400 || response.status() == 500);
+    }
+
+    /**
+     * Tests retrieving the bonds between a source and a validator.
+     * This test verifies:
+     * 1. Proper handling of valid addresses, with and without an epoch (expect 500 with mock client)
+     * 2. Proper rejection of an invalid epoch, source or validator address
+     */
+    #[tokio::test]
+    async fn test_get_bonds() {
+        let state = setup_test_client().await;
+        
+        // Create the filter for testing
+        let bonds_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("bonds"))
+            .and(warp::path::param::<String>())
+            .and(warp::path("to"))
+            .and(warp::path::param::<String>())
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|source: String, validator: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_bonds(state, source, validator, query).await
+            });
+        
+        // Test with valid-format addresses
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/bonds/{}/to/{}", sample_address(1), sample_address(2)))
+            .reply(&bonds_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        // Test with an explicit epoch, and an invalid one
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/bonds/{}/to/{}?epoch=10", sample_address(1), sample_address(2)))
+            .reply(&bonds_route)
+            .await;
+        
+        assert!(response.status() == 500);
+
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/bonds/{}/to/{}?epoch=latest", sample_address(1), sample_address(2)))
+            .reply(&bonds_route)
+            .await;
+        
+        assert_eq!(response.status(), 400);
+
+        // Test with an invalid source address
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/bonds/invalid-address/to/{}", sample_address(2)))
+            .reply(&bonds_route)
+            .await;
+        
+        // Should be either 400 Bad Request or 500 Internal Server Error
+        // depending on how address validation is implemented
+        assert!(response.status() == 400 || response.status() == 500);
+
+        // Test with an invalid validator address
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/bonds/{}/to/invalid-address", sample_address(1)))
+            .reply(&bonds_route)
+            .await;
+        
+        assert!(response.status() == 400 || response.status() == 500);
+    }
+
+    /**
+     * Tests retrieving the unbonds between a source and a validator.
+     * This test verifies:
+     * 1. Proper handling of valid addresses (expect 500 with mock client)
+     * 2. Proper rejection of an invalid source or validator address
+     */
+    #[tokio::test]
+    async fn test_get_unbonds() {
+        let state = setup_test_client().await;
+        
+        // Create the filter for testing
+        let unbonds_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("unbonds"))
+            .and(warp::path::param::<String>())
+            .and(warp::path("to"))
+            .and(warp::path::param::<String>())
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|source: String, validator: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_unbonds(state, source, validator, query).await
+            });
+        
+        // Test with valid-format addresses
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/unbonds/{}/to/{}", sample_address(1), sample_address(2)))
+            .reply(&unbonds_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        // Test with an invalid source address
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/unbonds/invalid-address/to/{}", sample_address(2)))
+            .reply(&unbonds_route)
+            .await;
+        
+        // Should be either 400 Bad Request or 500 Internal Server Error
+        // depending on how address validation is implemented
+        assert!(response.status() == 400 || response.status() == 500);
+
+        // Test with an invalid validator address
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/unbonds/{}/to/invalid-address", sample_address(1)))
+            .reply(&unbonds_route)
+            .await;
+        
+        assert!(response.status() == 400 || response.status() == 500);
+    }
+
+    /**
+     * Tests retrieving withdrawable tokens for a source at a single validator and
+     * across all validators.
+     * This test verifies:
+     * 1. Proper handling of valid addresses (expect 500 with mock client)
+     * 2. Proper rejection of invalid address format
+     */
+    #[tokio::test]
+    async fn test_get_withdrawable() {
+        let state = setup_test_client().await;
+        
+        // Create the filters for testing
+        let withdrawable_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("withdrawable"))
+            .and(warp::path::param::<String>())
+            .and(warp::path::param::<String>())
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|source: String, validator: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_withdrawable(state, source, validator, query).await
+            });
+        let source_withdrawable_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("withdrawable"))
+            .and(warp::path::param::<String>())
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|source: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_source_withdrawable(state, source, query).await
+            });
+        
+        // Test with valid-format addresses
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/withdrawable/{}/{}", sample_address(1), sample_address(2)))
+            .reply(&withdrawable_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/withdrawable/{}", sample_address(1)))
+            .reply(&source_withdrawable_route)
+            .await;
+        
+        assert!(response.status() == 500);
+
+        // Test with invalid addresses
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/withdrawable/invalid-address/{}", sample_address(2)))
+            .reply(&withdrawable_route)
+            .await;
+        
+        // Should be either 400 Bad Request or 500 Internal Server Error
+        // depending on how address validation is implemented
+        assert!(response.status() == 400 || response.status() == 500);
+
+        let response = request()
+            .method("GET")
+            .path("/api/pos/withdrawable/invalid-address")
+            .reply(&source_withdrawable_route)
+            .await;
+        
+        assert!(response.status() == 400 || response.status() == 500);
+    }
+
+    /**
+     * Tests splitting unbonds into amounts withdrawable now and later.
+     * This test verifies:
+     * 1. Unbonds whose withdraw epoch has been reached are summed as withdrawable
+     * 2. Later unbonds are grouped and summed by withdraw epoch
+     * 3. Slashed amounts are excluded
+     */
+    #[test]
+    fn test_withdrawal_schedule() {
+        use namada_core::chain::Epoch;
+        use namada_core::token::Amount;
+        use namada_proof_of_stake::types::UnbondDetails;
+
+        let unbond = |start: u64, withdraw: u64, amount: u64, slashed: Option<u64>| UnbondDetails {
+            start: Epoch(start),
+            withdraw: Epoch(withdraw),
+            amount: Amount::from(amount),
+            slashed_amount: slashed.map(Amount::from),
+        };
+        let unbonds = vec![
+            unbond(1, 10, 100, None),
+            unbond(2, 12, 50, Some(20)),
+            unbond(3, 15, 40, None),
+            unbond(4, 15, 60, None),
+            unbond(5, 20, 10, None),
+        ];
+
+        let (withdrawable, pending) = withdrawal_schedule(&unbonds, Epoch(12)).unwrap();
+
+        assert_eq!(withdrawable, Amount::from(130));
+        assert_eq!(
+            pending.into_iter().collect::<Vec<_>>(),
+            vec![(Epoch(15), Amount::from(100)), (Epoch(20), Amount::from(10))]
+        );
+    }
+
+    /**
+     * Tests retrieving unclaimed rewards for a delegator and for a validator's self-bond.
+     * This test verifies:
+     * 1. Proper handling of valid addresses (expect 500 with mock client)
+     * 2. Proper rejection of invalid validator or delegator address
+     */
+    #[tokio::test]
+    async fn test_get_rewards() {
+        let state = setup_test_client().await;
+        
+        // Create the filters for testing
+        let rewards_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("rewards"))
+            .and(warp::path::param::<String>())
+            .and(warp::path::param::<String>())
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|validator: String, delegator: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_rewards(state, validator, Some(delegator), query).await
+            });
+        let self_bond_rewards_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("rewards"))
+            .and(warp::path::param::<String>())
+            .and(warp::path::end())
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|validator: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_rewards(state, validator, None, query).await
+            });
+        
+        // Test with valid-format addresses
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/rewards/{}/{}", sample_address(1), sample_address(2)))
+            .reply(&rewards_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/rewards/{}", sample_address(1)))
+            .reply(&self_bond_rewards_route)
+            .await;
+        
+        assert!(response.status() == 500);
+
+        // Test with an invalid delegator address
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/rewards/{}/invalid-address", sample_address(1)))
+            .reply(&rewards_route)
+            .await;
+        
+        // Should be either 400 Bad Request or 500 Internal Server Error
+        // depending on how address validation is implemented
+        assert!(response.status() == 400 || response.status() == 500);
+
+        // Test with an invalid validator address
+        let response = request()
+            .method("GET")
+            .path("/api/pos/rewards/invalid-address")
+            .reply(&self_bond_rewards_route)
+            .await;
+        
+        assert!(response.status() == 400 || response.status() == 500);
+    }
+
+    /**
+     * Tests retrieving unclaimed rewards of a delegator across all its validators.
+     * This test verifies:
+     * 1. Proper handling of a valid address (expect 500 with mock client)
+     * 2. Proper rejection of invalid address format
+     */
+    #[tokio::test]
+    async fn test_get_total_rewards() {
+        let state = setup_test_client().await;
+        
+        // Create the filter for testing
+        let total_rewards_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("total_rewards"))
+            .and(warp::path::param::<String>())
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|delegator: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_total_rewards(state, delegator, query).await
+            });
+        
+        // Test with a valid-format address
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/total_rewards/{}", sample_address(1)))
+            .reply(&total_rewards_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        // Test with an invalid address
+        let response = request()
+            .method("GET")
+            .path("/api/pos/total_rewards/invalid-address")
+            .reply(&total_rewards_route)
+            .await;
+        
+        // Should be either 400 Bad Request or 500 Internal Server Error
+        // depending on how address validation is implemented
+        assert!(response.status() == 400 || response.status() == 500);
+    }
+
+    /**
+     * Tests retrieving the slashes applied to a validator.
+     * This test verifies:
+     * 1. Proper handling of valid address format (expect 500 with mock client)
+     * 2. Proper rejection of invalid address format
+     */
+    #[tokio::test]
+    async fn test_get_validator_slashes() {
+        let state = setup_test_client().await;
+        
+        // Create the filter for testing
+        let validator_slashes_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("validators"))
+            .and(warp::path::param::<String>())
+            .and(warp::path("slashes"))
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|address: String, query: EpochQuery, state: Arc<AppState>| async move {
+                get_validator_slashes(state, address, query).await
+            });
+        
+        // Test with a valid-format address
+        let response = request()
+            .method("GET")
+            .path(&format!("/api/pos/validators/{}/slashes", sample_address(1)))
+            .reply(&validator_slashes_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        // Test with an invalid address
+        let response = request()
+            .method("GET")
+            .path("/api/pos/validators/invalid-address/slashes")
+            .reply(&validator_slashes_route)
+            .await;
+        
+        // Should be either 400 Bad Request or 500 Internal Server Error
+        // depending on how address validation is implemented
+        assert!(response.status() == 400 || response.status() == 500);
+    }
+
+    /**
+     * Tests retrieving the slashes applied to all validators.
+     * Since we're using a mock client, we expect a 500 error.
+     */
+    #[tokio::test]
+    async fn test_get_all_slashes() {
+        let state = setup_test_client().await;
+        
+        // Create the filter for testing
+        let slashes_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("slashes"))
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+                get_all_slashes(state, query).await
+            });
+        
+        // Test the endpoint
+        let response = request()
+            .method("GET")
+            .path("/api/pos/slashes")
+            .reply(&slashes_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+    }
+
+    /**
+     * Tests retrieving the total bonded stake and staking ratio.
+     * Since we're using a mock client, we expect a 500 error with and without an epoch.
+     */
+    #[tokio::test]
+    async fn test_get_total_stake() {
+        let state = setup_test_client().await;
+        
+        // Create the filter for testing
+        let total_stake_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("total_stake"))
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+                get_total_stake(state, query).await
+            });
+        
+        // Test the endpoint
+        let response = request()
+            .method("GET")
+            .path("/api/pos/total_stake")
+            .reply(&total_stake_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        // Test with an explicit epoch
+        let response = request()
+            .method("GET")
+            .path("/api/pos/total_stake?epoch=10")
+            .reply(&total_stake_route)
+            .await;
+        
+        assert!(response.status() == 500);
+    }
+
+    /**
+     * Tests computing the staking ratio, including an empty supply.
+     */
+    #[test]
+    fn test_staking_ratio() {
+        use namada_core::dec::Dec;
+        use namada_core::token::Amount;
+
+        let ratio = staking_ratio(Amount::from(400), Amount::from(1000)).unwrap();
+        assert_eq!(ratio, Dec::new(4, 1).unwrap());
+
+        let ratio = staking_ratio(Amount::zero(), Amount::zero()).unwrap();
+        assert!(ratio.is_zero());
+    }
+
+    /**
+     * Tests that slash types are named independently of their Rust names.
+     */
+    #[test]
+    fn test_slash_type_name() {
+        use namada_proof_of_stake::types::SlashType;
+        use crate::models::pos::slash_type_name;
+
+        assert_eq!(slash_type_name(&SlashType::DuplicateVote), "duplicate_vote");
+        assert_eq!(slash_type_name(&SlashType::LightClientAttack), "light_client_attack");
+    }
+
+    /**
+     * Tests retrieving the PoS parameters.
+     * Since we're using a mock client, we expect a 500 error with and without
+     * an epoch, and a 400 for an invalid epoch.
+     */
+    #[tokio::test]
+    async fn test_get_pos_parameters() {
+        let state = setup_test_client().await;
+        
+        // Create the filter for testing
+        let parameters_route = warp::path("api")
+            .and(warp::path("pos"))
+            .and(warp::path("parameters"))
+            .and(warp::get())
+            .and(warp::query::<EpochQuery>())
+            .and(with_state(state.clone()))
+            .and_then(|query: EpochQuery, state: Arc<AppState>| async move {
+                get_pos_parameters(state, query).await
+            });
+        
+        // Test the endpoint
+        let response = request()
+            .method("GET")
+            .path("/api/pos/parameters")
+            .reply(&parameters_route)
+            .await;
+        
+        // The response should be an error since we're using a mock URL
+        assert!(response.status() == 500);
+
+        // An explicit epoch is checked against the current one
+        let response = request()
+            .method("GET")
+            .path("/api/pos/parameters?epoch=10")
+            .reply(&parameters_route)
+            .await;
+        
+        assert!(response.status() == 500);
+
+        // Test with an invalid epoch
+        let response = request()
+            .method("GET")
+            .path("/api/pos/parameters?epoch=latest")
+            .reply(&parameters_route)
+            .await;
+        
+        assert_eq!(response.status(), 400);
+    }
+}
@@ -0,0 +1,226 @@
+/**
+ * Rate Limiting Tests
+ * 
+ * This module contains tests for the token bucket rate limiting of requests:
+ * - Per-IP quotas of each route class
+ * - Per-API-key quotas, with the key sent in the X-API-Key header
+ * - Client addresses forwarded by trusted proxies
+ * 
+ * These tests verify that:
+ * 1. Requests over the quota are rejected with 429 and a Retry-After header
+ * 2. Each client and route class has its own bucket
+ * 3. Unknown API keys are rejected with 401
+ * 4. Requests without a client address or API key are rejected with 400
+ */
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use clap::Parser;
+    use warp::test::request;
+    use warp::Filter;
+    use crate::config::{CliArgs, Config};
+    use crate::models::error::handle_rejection;
+    use crate::rate_limit::{rate_limit, RateLimiter, RouteClass};
+
+    /**
+     * Creates a rate limiter from a TOML config file.
+     */
+    fn setup_rate_limiter(name: &str, contents: &str) -> Arc<RateLimiter> {
+        let path = std::env::temp_dir().join(format!("namada-api-{}-{}", std::process::id(), name));
+        std::fs::write(&path, contents).expect("Failed to write config file");
+        let args = CliArgs::parse_from(["namada-api", "--config", path.to_str().unwrap()]);
+        let config = Config::load(args).expect("Failed to load config file");
+        std::fs::remove_file(&path).ok();
+        
+        Arc::new(RateLimiter::new(&config))
+    }
+
+    /**
+     * Tests the class of the routes, the routes querying many validators,
+     * delegations, proposals or votes at once being heavy.
+     */
+    #[test]
+    fn test_route_class() {
+        for path in [
+            "/api/pos/validators",
+            "/api/pos/validator_set/consensus",
+            "/api/pos/liveness_info",
+            "/api/pos/withdrawable/tnam1qxyz",
+            "/api/pos/total_rewards/tnam1qxyz",
+            "/api/gov/proposals",
+            "/api/gov/proposals/12",
+            "/api/gov/proposals/12/votes",
+            "/api/gov/proposals/12/votes/tnam1qxyz",
+            "/api/stream/validators/tnam1qxyz",
+        ] {
+            assert_eq!(RouteClass::of(path), RouteClass::Heavy, "{}", path);
+        }
+        for path in [
+            "/api/pos/withdrawable/tnam1qxyz/tnam1qabc",
+            "/api/pos/rewards/tnam1qxyz",
+            "/api/pos/validator/tnam1qxyz/bonds",
+            "/api/blocks/latest",
+        ] {
+            assert_eq!(RouteClass::of(path), RouteClass::Standard, "{}", path);
+        }
+        for path in ["/api/health/api_status", "/metrics", "/api/docs"] {
+            assert_eq!(RouteClass::of(path), RouteClass::Health, "{}", path);
+        }
+    }
+
+    /**
+     * Tests that clients are limited per IP address and route class.
+     */
+    #[tokio::test]
+    async fn test_rate_limit_per_ip() {
+        let limiter = setup_rate_limiter("rate-limit-ip.toml", r#"
+[rate_limits.heavy]
+requests_per_second = 0.1
+burst = 2
+"#);
+        let routes = rate_limit(limiter)
+            .and(warp::path!("api" / "pos" / ..))
+            .map(|| "ok")
+            .recover(handle_rejection);
+        let get = |path: &'static str, addr: &'static str| request()
+            .method("GET")
+            .path(path)
+            .remote_addr(addr.parse().unwrap())
+            .reply(&routes);
+        
+        // The burst of the heavy class is used up
+        assert_eq!(get("/api/pos/validators", "192.0.2.1:4000").await.status(), 200);
+        assert_eq!(get("/api/pos/validators_details", "192.0.2.1:4001").await.status(), 200);
+        let response = get("/api/pos/validators", "192.0.2.1:4000").await;
+        assert_eq!(response.status(), 429);
+        assert_eq!(response.headers()["retry-after"], "10");
+        
+        // Other classes and other clients have their own buckets
+        assert_eq!(get("/api/pos/validator/tnam1qxyz/bonds", "192.0.2.1:4000").await.status(), 200);
+        assert_eq!(get("/api/pos/validators", "192.0.2.2:4000").await.status(), 200);
+        
+        // IPv6 clients are limited by /64 network
+        assert_eq!(get("/api/pos/validators", "[2001:db8::1]:4000").await.status(), 200);
+        assert_eq!(get("/api/pos/validators", "[2001:db8::2]:4000").await.status(), 200);
+        assert_eq!(get("/api/pos/validators", "[2001:db8::3]:4000").await.status(), 429);
+    }
+
+    /**
+     * Tests that clients sending an API key are limited per key, with the
+     * quotas of the key, and that unknown keys are rejected.
+     */
+    #[tokio::test]
+    async fn test_rate_limit_per_api_key() {
+        let limiter = setup_rate_limiter("rate-limit-key.toml", r#"
+[rate_limits.heavy]
+requests_per_second = 0.1
+burst = 1
+
+[[api_keys]]
+name = "explorer"
+key = "explorer-key"
+
+[api_keys.rate_limits.heavy]
+requests_per_second = 0.1
+burst = 3
+"#);
+        let routes = rate_limit(limiter)
+            .and(warp::path!("api" / "pos" / ..))
+            .map(|| "ok")
+            .recover(handle_rejection);
+        let get = |api_key: Option<&'static str>, addr: &'static str| {
+            let mut builder = request()
+                .method("GET")
+                .path("/api/pos/validators")
+                .remote_addr(addr.parse().unwrap());
+            if let Some(api_key) = api_key {
+                builder = builder.header("x-api-key", api_key);
+            }
+            builder.reply(&routes)
+        };
+        
+        // The key is shared by the clients using it
+        assert_eq!(get(Some("explorer-key"), "192.0.2.1:4000").await.status(), 200);
+        assert_eq!(get(Some("explorer-key"), "192.0.2.2:4000").await.status(), 200);
+        assert_eq!(get(Some("explorer-key"), "192.0.2.3:4000").await.status(), 200);
+        assert_eq!(get(Some("explorer-key"), "192.0.2.1:4000").await.status(), 429);
+        
+        // Without a key, the per-IP quota applies
+        assert_eq!(get(None, "192.0.2.1:4000").await.status(), 200);
+        assert_eq!(get(None, "192.0.2.1:4000").await.status(), 429);
+        
+        // Unknown keys are rejected
+        assert_eq!(get(Some("stolen-key"), "192.0.2.4:4000").await.status(), 401);
+    }
+
+    /**
+     * Tests that clients behind a trusted proxy are limited by the address
+     * the proxy forwarded, and that the headers of other peers are ignored.
+     */
+    #[tokio::test]
+    async fn test_rate_limit_trusted_proxies() {
+        let limiter = setup_rate_limiter("rate-limit-proxies.toml", r#"
+trusted_proxies = ["10.0.0.0/8", "2001:db8::1"]
+
+[rate_limits.heavy]
+requests_per_second = 0.1
+burst = 1
+"#);
+        let routes = rate_limit(limiter)
+            .and(warp::path!("api" / "pos" / ..))
+            .map(|| "ok")
+            .recover(handle_rejection);
+        let get = |addr: &'static str, header: &'static str, value: &'static str| request()
+            .method("GET")
+            .path("/api/pos/validators")
+            .remote_addr(addr.parse().unwrap())
+            .header(header, value)
+            .reply(&routes);
+        
+        // Clients behind the proxies have their own buckets, the proxies
+        // appended by trusted ones being skipped
+        assert_eq!(get("10.0.0.1:4000", "x-forwarded-for", "192.0.2.1").await.status(), 200);
+        assert_eq!(get("10.0.0.2:4000", "x-forwarded-for", "192.0.2.2, 10.1.0.1").await.status(), 200);
+        assert_eq!(get("[2001:db8::1]:4000", "forwarded", r#"for="[2001:db8:1::1]:4711";proto=https"#).await.status(), 200);
+        assert_eq!(get("10.0.0.1:4000", "forwarded", "for=192.0.2.1:4711").await.status(), 429);
+        
+        // Addresses forwarded by clients in front of the proxy aren't trusted
+        assert_eq!(get("10.0.0.1:4000", "x-forwarded-for", "192.0.2.2, 192.0.2.3").await.status(), 200);
+        assert_eq!(get("10.0.0.1:4000", "x-forwarded-for", "192.0.2.4, 192.0.2.3").await.status(), 429);
+        
+        // Untrusted peers are limited by their own address
+        assert_eq!(get("192.0.2.5:4000", "x-forwarded-for", "192.0.2.6").await.status(), 200);
+        assert_eq!(get("192.0.2.5:4000", "x-forwarded-for", "192.0.2.7").await.status(), 429);
+    }
+
+    /**
+     * Tests that requests without a client address are rejected with 400 on
+     * limited routes, unless they send an API key.
+     */
+    #[tokio::test]
+    async fn test_rate_limit_unknown_address() {
+        let limiter = setup_rate_limiter("rate-limit-unknown.toml", r#"
+[[api_keys]]
+name = "explorer"
+key = "explorer-key"
+"#);
+        let routes = rate_limit(limiter)
+            .and(warp::path!("api" / ..))
+            .map(|| "ok")
+            .recover(handle_rejection);
+        
+        let response = request().method("GET").path("/api/pos/validators").reply(&routes).await;
+        assert_eq!(response.status(), 400);
+        let response = request()
+            .method("GET")
+            .path("/api/pos/validators")
+            .header("x-api-key", "explorer-key")
+            .reply(&routes)
+            .await;
+        assert_eq!(response.status(), 200);
+        
+        // Routes without a quota aren't limited
+        let response = request().method("GET").path("/api/health/live").reply(&routes).await;
+        assert_eq!(response.status(), 200);
+    }
+}
@@ -0,0 +1,169 @@
+/**
+ * Uptime Monitor Tests
+ *
+ * This module contains tests for the tracking of the uptime of validators
+ * by the background uptime monitor.
+ *
+ * These tests verify that:
+ * 1. Uptime is only computed over windows fully observed
+ * 2. Validators are alerted on once when they cross the alert threshold,
+ *    and once when they recover
+ * 3. The history is started over after a gap in the recorded blocks
+ * 4. Validators leaving the consensus set are reported once, and their
+ *    history and at-risk state dropped
+ */
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use namada_core::address::{Address, EstablishedAddress};
+    use crate::models::block::{BlockSignaturesResponse, SignatureStatus, ValidatorSignature};
+    use crate::uptime::{is_watched, UptimeEvent, UptimeTracker, WindowUptime};
+
+    /**
+     * Creates the signatures of a block, from the CometBFT address of each
+     * validator and whether it signed.
+     */
+    fn block_signatures(height: u64, validators: &[(&str, bool)]) -> BlockSignaturesResponse {
+        BlockSignaturesResponse {
+            height,
+            canonical: true,
+            total_voting_power: validators.len() as u64,
+            signed_voting_power: validators.iter().filter(|(_, signed)| *signed).count() as u64,
+            signatures: validators.iter()
+                .map(|(address, signed)| ValidatorSignature {
+                    validator: None,
+                    consensus_address: address.to_string(),
+                    status: if *signed { SignatureStatus::Signed } else { SignatureStatus::Absent },
+                    voting_power: 1,
+                })
+                .collect(),
+        }
+    }
+
+    /**
+     * Tests that the uptime over a window is only known once the window was
+     * fully observed, and only counts the blocks the validator was in the
+     * set of.
+     */
+    #[test]
+    fn test_uptime_windows() {
+        let mut tracker = UptimeTracker::new(vec![4, 8]);
+        for height in 1..=4 {
+            tracker.record(&block_signatures(height, &[("A", height != 2), ("B", true)]));
+        }
+        assert_eq!(tracker.last_height(), Some(4));
+        assert_eq!(tracker.uptimes("A"), vec![
+            WindowUptime { window: 4, uptime: Some(0.75) },
+            WindowUptime { window: 8, uptime: None },
+        ]);
+
+        // C joins the set at height 7
+        for height in 5..=8 {
+            let mut validators = vec![("A", true), ("B", height % 2 == 0)];
+            if height >= 7 {
+                validators.push(("C", height == 8));
+            }
+            tracker.record(&block_signatures(height, &validators));
+        }
+        assert_eq!(tracker.uptimes("A"), vec![
+            WindowUptime { window: 4, uptime: Some(1.0) },
+            WindowUptime { window: 8, uptime: Some(0.875) },
+        ]);
+        assert_eq!(tracker.uptimes("B")[0].uptime, Some(0.5));
+        assert_eq!(tracker.uptimes("C")[1].uptime, Some(0.5));
+        assert_eq!(tracker.uptimes("D")[1].uptime, None);
+    }
+
+    /**
+     * Tests that a validator is alerted on when its uptime over any window
+     * falls below the threshold, and when it is above it over every window.
+     */
+    #[test]
+    fn test_uptime_alerts() {
+        let mut tracker = UptimeTracker::new(vec![2, 4]);
+        let mut height = 0;
+        let mut record = |tracker: &mut UptimeTracker, signed: bool| {
+            height += 1;
+            tracker.record(&block_signatures(height, &[("A", signed)]));
+        };
+
+        // No alert until a window is observed
+        record(&mut tracker, false);
+        assert_eq!(tracker.update("A", 0.95), None);
+
+        record(&mut tracker, true);
+        let (event, uptimes) = tracker.update("A", 0.95).unwrap();
+        assert_eq!(event, UptimeEvent::ValidatorAtRisk);
+        assert_eq!(uptimes[0].uptime, Some(0.5));
+
+        // Still at risk over the longest window
+        record(&mut tracker, true);
+        record(&mut tracker, true);
+        assert_eq!(tracker.update("A", 0.95), None);
+
+        record(&mut tracker, true);
+        let (event, uptimes) = tracker.update("A", 0.95).unwrap();
+        assert_eq!(event, UptimeEvent::ValidatorRecovered);
+        assert_eq!(uptimes[1].uptime, Some(1.0));
+        assert_eq!(tracker.update("A", 0.95), None);
+    }
+
+    /**
+     * Tests that the history is started over after a gap in the blocks.
+     */
+    #[test]
+    fn test_uptime_gap() {
+        let mut tracker = UptimeTracker::new(vec![2]);
+        tracker.record(&block_signatures(1, &[("A", false)]));
+        tracker.record(&block_signatures(2, &[("A", false)]));
+        assert_eq!(tracker.uptimes("A")[0].uptime, Some(0.0));
+
+        tracker.record(&block_signatures(10, &[("A", true)]));
+        assert_eq!(tracker.uptimes("A")[0].uptime, None);
+        tracker.record(&block_signatures(11, &[("A", true)]));
+        assert_eq!(tracker.uptimes("A")[0].uptime, Some(1.0));
+    }
+
+    /**
+     * Tests that a validator leaving the consensus set is reported once, with
+     * its uptime, and that it is no longer at risk nor tracked.
+     */
+    #[test]
+    fn test_uptime_left_set() {
+        let validator_a = Address::Established(EstablishedAddress::from([1; 20]));
+        let validator_b = Address::Established(EstablishedAddress::from([2; 20]));
+        let both = HashMap::from([("A".to_string(), validator_a.clone()), ("B".to_string(), validator_b.clone())]);
+        let mut tracker = UptimeTracker::new(vec![2]);
+        assert!(tracker.update_set(both.clone()).is_empty());
+
+        tracker.record(&block_signatures(1, &[("A", false), ("B", true)]));
+        tracker.record(&block_signatures(2, &[("A", false), ("B", true)]));
+        assert_eq!(tracker.update("A", 0.95).unwrap().0, UptimeEvent::ValidatorAtRisk);
+
+        // A is jailed while at risk
+        tracker.record(&block_signatures(3, &[("B", true)]));
+        let left = tracker.update_set(HashMap::from([("B".to_string(), validator_b.clone())]));
+        assert_eq!(left, vec![("A".to_string(), validator_a.clone(), vec![WindowUptime { window: 2, uptime: Some(0.0) }])]);
+        assert_eq!(tracker.uptimes("A")[0].uptime, None);
+        assert!(tracker.update_set(HashMap::from([("B".to_string(), validator_b.clone())])).is_empty());
+
+        // Back in the set, its uptime is tracked afresh, without recovering
+        tracker.update_set(both);
+        tracker.record(&block_signatures(4, &[("A", true), ("B", true)]));
+        assert_eq!(tracker.update("A", 0.95), None);
+        tracker.record(&block_signatures(5, &[("A", false), ("B", true)]));
+        assert_eq!(tracker.update("A", 0.95).unwrap().0, UptimeEvent::ValidatorAtRisk);
+    }
+
+    /**
+     * Tests that every validator is watched when none is configured.
+     */
+    #[test]
+    fn test_is_watched() {
+        let validator = Address::Established(EstablishedAddress::from([1; 20]));
+        let other = Address::Established(EstablishedAddress::from([2; 20]));
+        assert!(is_watched(&[], &validator));
+        assert!(is_watched(std::slice::from_ref(&validator), &validator));
+        assert!(!is_watched(&[other], &validator));
+    }
+}
@@ -0,0 +1,331 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use futures::StreamExt;
+use serde::Serialize;
+use tracing::{info, warn};
+use namada_core::address::Address;
+use namada_core::chain::BlockHeight;
+use crate::client::{ClientError, NamadaClient};
+use crate::config::UptimeMonitorConfig;
+use crate::models::block::{BlockSignaturesResponse, SignatureStatus};
+
+/// Maximum number of blocks whose signatures are fetched concurrently
+const FETCH_CONCURRENCY: usize = 8;
+
+/// Transition of a watched validator
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UptimeEvent {
+    /// Its uptime over a window fell below the alert threshold
+    ValidatorAtRisk,
+    /// Its uptime is above the alert threshold over every window again
+    ValidatorRecovered,
+    /// It left the consensus set, its uptime being tracked afresh if it joins
+    /// it again
+    #[serde(rename = "validator_left_set")]
+    LeftSet,
+}
+
+/// Uptime of a validator over a window
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct WindowUptime {
+    /// Length of the window, in blocks
+    pub window: u64,
+    /// Share of the blocks of the window the validator signed, out of the
+    /// ones it was in the validator set of. Null until the monitor observed a
+    /// whole window, or if the validator wasn't in the set during the window.
+    pub uptime: Option<f64>,
+}
+
+/// Payload POSTed to the webhooks
+#[derive(Debug, Serialize)]
+pub struct UptimeAlert {
+    pub event: UptimeEvent,
+    pub validator: String,
+    pub consensus_address: String,
+    /// Last block the uptime was computed at
+    pub height: u64,
+    pub liveness_threshold: String,
+    /// `liveness_threshold + alert_margin`
+    pub alert_threshold: f64,
+    pub uptimes: Vec<WindowUptime>,
+    /// Votes missed in the on-chain liveness window, null once the validator
+    /// left the consensus set
+    pub missed_votes: Option<u64>,
+    pub liveness_window_len: u64,
+}
+
+/// Signing history of the validators over the last blocks
+pub struct UptimeTracker {
+    windows: Vec<u64>,
+    /// First block of the contiguous range of recorded blocks
+    first_height: Option<u64>,
+    last_height: Option<u64>,
+    /// Blocks each validator was in the set of, and whether it signed them,
+    /// oldest first, by CometBFT address
+    histories: HashMap<String, VecDeque<(u64, bool)>>,
+    /// Validators below the alert threshold, by CometBFT address
+    at_risk: HashSet<String>,
+    /// Native address of the validators of the consensus set, as last
+    /// updated, by CometBFT address
+    members: HashMap<String, Address>,
+}
+
+impl UptimeTracker {
+    pub fn new(windows: Vec<u64>) -> Self {
+        Self {
+            windows,
+            first_height: None,
+            last_height: None,
+            histories: HashMap::new(),
+            at_risk: HashSet::new(),
+            members: HashMap::new(),
+        }
+    }
+
+    /// Last block recorded
+    pub fn last_height(&self) -> Option<u64> {
+        self.last_height
+    }
+
+    fn max_window(&self) -> u64 {
+        self.windows.iter().copied().max().unwrap_or_default()
+    }
+
+    /// Record the signatures of a block. When it doesn't follow the last
+    /// block recorded, the history is started over from it.
+    pub fn record(&mut self, signatures: &BlockSignaturesResponse) {
+        let height = signatures.height;
+        if self.last_height.is_none_or(|last_height| height != last_height + 1) {
+            self.histories.clear();
+            self.first_height = Some(height);
+        }
+        self.last_height = Some(height);
+
+        for signature in &signatures.signatures {
+            self.histories.entry(signature.consensus_address.clone())
+                .or_default()
+                .push_back((height, signature.status == SignatureStatus::Signed));
+        }
+
+        // Forget the blocks out of the longest window
+        let oldest = height.saturating_sub(self.max_window());
+        self.histories.retain(|_, history| {
+            while history.front().is_some_and(|(height, _)| *height <= oldest) {
+                history.pop_front();
+            }
+            !history.is_empty()
+        });
+    }
+
+    /// Uptime of a validator over each window
+    pub fn uptimes(&self, consensus_address: &str) -> Vec<WindowUptime> {
+        let history = self.histories.get(consensus_address);
+        self.windows.iter()
+            .map(|&window| {
+                let uptime = match (self.first_height, self.last_height, history) {
+                    (Some(first_height), Some(last_height), Some(history)) if last_height - first_height + 1 >= window => {
+                        let oldest = last_height - window;
+                        let (blocks, signed) = history.iter()
+                            .filter(|(height, _)| *height > oldest)
+                            .fold((0u64, 0u64), |(blocks, signed), (_, has_signed)| (blocks + 1, signed + u64::from(*has_signed)));
+                        (blocks > 0).then(|| signed as f64 / blocks as f64)
+                    }
+                    _ => None,
+                };
+                WindowUptime { window, uptime }
+            })
+            .collect()
+    }
+
+    /// Update whether a validator is at risk, from its uptime over the
+    /// windows observed, and return the event of its transition
+    pub fn update(&mut self, consensus_address: &str, alert_threshold: f64) -> Option<(UptimeEvent, Vec<WindowUptime>)> {
+        let uptimes = self.uptimes(consensus_address);
+        let observed: Vec<_> = uptimes.iter().filter_map(|uptime| uptime.uptime).collect();
+        if observed.is_empty() {
+            return None;
+        }
+
+        let at_risk = observed.iter().any(|uptime| *uptime < alert_threshold);
+        let was_at_risk = self.at_risk.contains(consensus_address);
+        match (was_at_risk, at_risk) {
+            (false, true) => {
+                self.at_risk.insert(consensus_address.to_string());
+                Some((UptimeEvent::ValidatorAtRisk, uptimes))
+            }
+            (true, false) => {
+                self.at_risk.remove(consensus_address);
+                Some((UptimeEvent::ValidatorRecovered, uptimes))
+            }
+            _ => None,
+        }
+    }
+
+    /// Update the validators of the consensus set, by CometBFT address, and
+    /// return the ones that left it since the last update, with their uptime
+    /// over each window. Their history and at-risk state are dropped.
+    pub fn update_set(&mut self, members: HashMap<String, Address>) -> Vec<(String, Address, Vec<WindowUptime>)> {
+        let left: Vec<_> = self.members.iter()
+            .filter(|(consensus_address, _)| !members.contains_key(*consensus_address))
+            .map(|(consensus_address, validator)| (consensus_address.clone(), validator.clone(), self.uptimes(consensus_address)))
+            .collect();
+        for (consensus_address, _, _) in &left {
+            self.histories.remove(consensus_address);
+        }
+        self.at_risk.retain(|consensus_address| members.contains_key(consensus_address));
+        self.members = members;
+        left
+    }
+}
+
+/// Background monitor of the uptime of validators, from the signatures of
+/// each new block, alerting the webhooks when a watched validator crosses
+/// the alert threshold
+pub struct UptimeMonitor {
+    client: Arc<NamadaClient>,
+    config: UptimeMonitorConfig,
+    http: reqwest::Client,
+    tracker: UptimeTracker,
+}
+
+impl UptimeMonitor {
+    pub fn new(client: Arc<NamadaClient>, config: UptimeMonitorConfig) -> Result<Self, reqwest::Error> {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_millis(config.webhook_timeout_ms))
+            .build()?;
+        Ok(Self {
+            client,
+            tracker: UptimeTracker::new(config.windows.clone()),
+            config,
+            http,
+        })
+    }
+
+    /// Poll the new blocks in the background
+    pub fn spawn(mut self) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_millis(self.config.poll_interval_ms));
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticker.tick().await;
+                if let Err(err) = self.poll().await {
+                    warn!("Failed to update the uptime of validators: {}", err);
+                }
+            }
+        })
+    }
+
+    /// Record the signatures of the blocks committed since the last poll,
+    /// at most the longest window, then alert on the validators leaving the
+    /// consensus set and the ones crossing the alert threshold
+    async fn poll(&mut self) -> Result<(), ClientError> {
+        // The commit of the last block only holds the signatures seen so far
+        let last_height = self.client.query_last_height().await?.0.saturating_sub(1);
+        let oldest = last_height.saturating_sub(self.tracker.max_window()) + 1;
+        let from = self.tracker.last_height().map_or(oldest, |height| (height + 1).max(oldest));
+        if from > last_height {
+            return Ok(());
+        }
+
+        let liveness_info = self.client.get_liveness_info().await?;
+        let addresses: HashMap<_, _> = liveness_info.validators.iter()
+            .map(|validator| (validator.comet_address.clone(), validator.native_address.clone()))
+            .collect();
+
+        let client = self.client.clone();
+        let mut blocks = futures::stream::iter(from..=last_height)
+            .map(|height| {
+                let client = client.clone();
+                async move {
+                    let (commit, validators) = futures::try_join!(
+                        client.get_commit(BlockHeight(height)),
+                        client.get_comet_validators(BlockHeight(height)),
+                    )?;
+                    Ok::<_, ClientError>((height, commit, validators))
+                }
+            })
+            .buffered(FETCH_CONCURRENCY);
+        while let Some(block) = blocks.next().await {
+            let (height, commit, validators) = block?;
+            let signatures = BlockSignaturesResponse::new(
+                height,
+                true,
+                &validators,
+                &commit.signed_header.commit.signatures,
+                &addresses,
+            );
+            self.tracker.record(&signatures);
+        }
+
+        let liveness_threshold = liveness_info.liveness_threshold.to_string();
+        let alert_threshold = liveness_threshold.parse::<f64>().unwrap_or_default() + self.config.alert_margin;
+        for (consensus_address, validator, uptimes) in self.tracker.update_set(addresses) {
+            if !is_watched(&self.config.validators, &validator) {
+                continue;
+            }
+
+            let alert = UptimeAlert {
+                event: UptimeEvent::LeftSet,
+                validator: validator.to_string(),
+                consensus_address,
+                height: last_height,
+                liveness_threshold: liveness_threshold.clone(),
+                alert_threshold,
+                uptimes,
+                missed_votes: None,
+                liveness_window_len: liveness_info.liveness_window_len,
+            };
+            warn!("Validator {} left the consensus set: {:?}", alert.validator, alert.uptimes);
+            self.notify(&alert).await;
+        }
+        for validator in &liveness_info.validators {
+            if !is_watched(&self.config.validators, &validator.native_address) {
+                continue;
+            }
+            let Some((event, uptimes)) = self.tracker.update(&validator.comet_address, alert_threshold) else {
+                continue;
+            };
+
+            let alert = UptimeAlert {
+                event,
+                validator: validator.native_address.to_string(),
+                consensus_address: validator.comet_address.clone(),
+                height: last_height,
+                liveness_threshold: liveness_threshold.clone(),
+                alert_threshold,
+                uptimes,
+                missed_votes: Some(validator.missed_votes),
+                liveness_window_len: liveness_info.liveness_window_len,
+            };
+            if event == UptimeEvent::ValidatorAtRisk {
+                warn!("Validator {} is at risk: {:?}", alert.validator, alert.uptimes);
+            } else {
+                info!("Validator {} recovered: {:?}", alert.validator, alert.uptimes);
+            }
+            self.notify(&alert).await;
+        }
+        Ok(())
+    }
+
+    /// POST an alert to every webhook
+    async fn notify(&self, alert: &UptimeAlert) {
+        let deliveries = self.config.webhooks.iter().map(|url| async move {
+            let result = self.http.post(url)
+                .json(alert)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(err) = result {
+                warn!("Failed to send uptime alert to webhook {}: {}", url, err);
+            }
+        });
+        futures::future::join_all(deliveries).await;
+    }
+}
+
+/// Whether a validator is watched, every validator being watched when none is configured
+pub fn is_watched(watched: &[Address], validator: &Address) -> bool {
+    watched.is_empty() || watched.contains(validator)
+}